@@ -0,0 +1,55 @@
+//! Compares solving large tables with discrete and compact constraints.
+//!
+//! Run with `cargo bench --bench compact`.
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use farc3::prelude::*;
+
+/// Constraints that each run of 3 consecutive variables sums to a multiple of `modulus`
+fn chain<C>(vars: usize, values: usize, modulus: usize) -> System<C>
+where
+  C: Constraint<Var = usize> + FromIterator<[(usize, usize); 3]> + std::hash::Hash + Eq,
+{
+  (0..vars - 2)
+    .map(|x| {
+      (0..values)
+        .flat_map(move |a| (0..values).flat_map(move |b| (0..values).map(move |c| [a, b, c])))
+        .filter(|values| values.iter().sum::<usize>() % modulus == 0)
+        .map(|[a, b, c]| [(x, a), (x + 1, b), (x + 2, c)])
+        .collect()
+    })
+    .collect()
+}
+
+/// The fastest of a few runs of `f`
+fn time<T>(mut f: impl FnMut() -> T) -> Duration {
+  (0..3)
+    .map(|_| {
+      let start = Instant::now();
+      black_box(f());
+      start.elapsed()
+    })
+    .min()
+    .expect("there's at least one run")
+}
+
+fn main() {
+  for (vars, values, modulus) in [(6, 12, 5), (6, 20, 7), (8, 24, 11)] {
+    let discrete = chain::<DiscreteConstraint<_, _>>(vars, values, modulus);
+    let compact = chain::<CompactConstraint<_, _>>(vars, values, modulus);
+    let size = compact.iter().next().map_or(0, Constraint::size);
+
+    let sltns = discrete.clone().solve().count();
+    assert_eq!(compact.clone().solve().count(), sltns);
+
+    let discrete = time(|| discrete.clone().solve().count());
+    let compact = time(|| compact.clone().solve().count());
+    println!(
+      "{vars} variables, {size} assignments per table, {sltns} solutions: \
+      discrete {discrete:?}, compact {compact:?} ({:.1}x)",
+      discrete.as_secs_f64() / compact.as_secs_f64()
+    );
+  }
+}
@@ -16,7 +16,44 @@
 /// // position [2, 3] is a mine
 /// // position [3, 3] is safe
 /// ```
-pub trait Assignment {
+pub trait Assignment: Sized {
+  /// The type of variables that are assigned
+  type Var;
+  /// The type of values that variables are assigned
+  type Value;
+  /// A variable that 2 assignments give contradicting values,\
+  /// along with the values that each assignment gives it
+  type Conflict;
+
+  /// The value assigned to `var`, if it's assigned
+  fn get(&self, var: &Self::Var) -> Option<&Self::Value>;
+
+  /// Whether `var` is assigned a value
+  fn contains(&self, var: &Self::Var) -> bool {
+    self.get(var).is_some()
+  }
+
+  /// The number of variables assigned
+  fn len(&self) -> usize;
+
+  /// Whether no variables are assigned
+  fn is_empty(&self) -> bool {
+    self.len() == 0
+  }
+
+  /// An iterator over each assigned variable and its value, in no particular order
+  fn iter(&self) -> impl Iterator<Item = (&Self::Var, &Self::Value)>;
+
+  /// Assigns a value to a variable, replacing any existing value
+  ///
+  /// ## Returns
+  ///
+  /// The value that `var` was previously assigned
+  fn insert(&mut self, var: Self::Var, value: Self::Value) -> Option<Self::Value>;
+
+  /// Removes all assigned variables, except for those in `vars`
+  fn restrict_to(self, vars: impl IntoIterator<Item = Self::Var>) -> Self;
+
   /// Calcultes the intersection of 2 solutions,\
   /// that only assigns a variable when:
   /// 1. the variable is assigned in both solutions
@@ -31,6 +68,15 @@ pub trait Assignment {
   /// ## Note
   ///
   /// Whilst this implementation does **slightly** hide contradiction errors,\
-  /// these are meant to be caught during constraint reduction instead.
+  /// these are meant to be caught during constraint reduction instead.\
+  /// Use [`Assignment::checked_union`] when contradictions should be reported.
   fn union(self, other: Self) -> Self;
+
+  /// Calculates the union of 2 solutions,\
+  /// failing when they assign contradicting values to any variable.
+  ///
+  /// ## Returns
+  ///
+  /// The union of both solutions, or all variables that they contradict on
+  fn checked_union(self, other: Self) -> Result<Self, Vec<Self::Conflict>>;
 }
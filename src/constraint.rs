@@ -1,7 +1,9 @@
 //! Traits for constraining values that variables can take
 
 use super::assignment::Assignment;
+use std::collections::{HashMap, HashSet};
 use std::fmt::Debug;
+use std::hash::Hash;
 
 /// A constraint that affects given variables in a system\
 /// A given constriant can be satisfied by multiple possible assignments.
@@ -12,6 +14,8 @@ use std::fmt::Debug;
 ///    i.e. if a constraint affects no variables, it should have 1 solution, the empty solution
 /// 2. if `this.variables().count() > 0`, then `this.decompositions().count() >= 1`\
 ///    i.e. if a constraint affects any variables, it should have at least 1 decomposition
+/// 3. each assignment that `self` allows agrees with exactly one of `self.decompositions()`\
+///    i.e. decompositions partition the solutions, so searches find each solution once
 ///
 /// ## Formal definition
 ///
@@ -39,7 +43,7 @@ pub trait Constraint {
   /// The type of variables assigned by the Constraint
   type Var;
   /// Any solution to a Constraint
-  type Solution: Assignment;
+  type Solution: Assignment<Var = Self::Var, Value: Hash + Eq + Clone>;
   /// The error raised when 2 constraints conflict with each other
   type ConflictErr: Debug;
 
@@ -58,6 +62,7 @@ pub trait Constraint {
   /// A decomposition is a constraint that:
   /// 1. affects a subset of `self.variables()`
   /// 2. has a single unique solution that `self` allows
+  /// 3. shares no solutions with the other decompositions
   ///
   /// ## Returns
   ///
@@ -82,4 +87,119 @@ pub trait Constraint {
 
   /// Pops all variables that have a unique assignment in this constraint
   fn pop_solution(&mut self) -> Option<Self::Solution>;
+
+  /// Whether an assignment satisfies this constraint.\
+  /// The assignment may be produced by any solver, not just a [`System`].
+  ///
+  /// ## Arguments
+  ///
+  /// - `solution`: the assignment to check, which may assign other variables too
+  ///
+  /// ## Returns
+  ///
+  /// Whether `solution` assigns all of `self.variables()`\
+  /// to values that `self` allows.
+  ///
+  /// [`System`]: crate::system::System
+  fn is_satisfied(&self, solution: &Self::Solution) -> bool;
+
+  /// A constraint that rules out the solution of one of `self.decompositions()`,\
+  /// whilst allowing every other assignment that `self` allows.\
+  /// This is used to permanently discard decompositions that lead to conflicts.
+  ///
+  /// ## Arguments
+  ///
+  /// - `decomposition`: a decomposition produced by `self.decompositions()`,\
+  ///   or a constraint produced by `self.split_on(..)`
+  ///
+  /// ## Returns
+  ///
+  /// The constraint to add alongside `self`,\
+  /// or `None` if the exclusion can't be represented (the default).
+  fn exclude(&self, decomposition: &Self) -> Option<Self>
+  where
+    Self: Sized,
+  {
+    let _ = decomposition;
+    None
+  }
+
+  /// Constraints that partition the assignments of `self` by the value of `var`,\
+  /// which can be used instead of decompositions to branch in a search.
+  ///
+  /// Each constraint should only allow assignments that `self` allows,\
+  /// assign a single value to `var` and share no assignments with the others.
+  ///
+  /// ## Returns
+  ///
+  /// A constraint for each value that `var` takes,\
+  /// or `None` if `self` can't be split on `var` (the default).
+  fn split_on(&self, var: &Self::Var) -> Option<Vec<Self>>
+  where
+    Self: Sized,
+  {
+    let _ = var;
+    None
+  }
+
+  /// Whether every assignment that `self` allows is also allowed by `other`,\
+  /// which makes `other` redundant alongside `self`.
+  ///
+  /// ## Returns
+  ///
+  /// Whether `self` entails `other`,\
+  /// which may be `false` when this can't be cheaply determined (the default).
+  fn subsumes(&self, other: &Self) -> bool {
+    let _ = other;
+    false
+  }
+
+  /// The values that each variable takes in some assignment that `self` allows.
+  ///
+  /// This lets a [`System`] share a domain for each variable between its constraints,\
+  /// so that values pruned by one constraint are pruned from all others that affect the variable.
+  ///
+  /// ## Returns
+  ///
+  /// A domain for each of `self.variables()` whose values are tracked,\
+  /// which is none of them by default.
+  ///
+  /// [`System`]: crate::system::System
+  fn supports(&self) -> Domains<Self::Var, <Self::Solution as Assignment>::Value> {
+    Domains::new()
+  }
+
+  /// Removes all assignments that give a variable a value outside of its domain.
+  ///
+  /// ## Arguments
+  ///
+  /// - `domains`: the values that variables can take,\
+  ///   where variables without a domain can take any value
+  ///
+  /// ## Returns
+  ///
+  /// Whether any assignments were removed (never, by default),\
+  /// or a conflict error if no assignments remain.
+  fn restrict(
+    &mut self,
+    domains: &Domains<Self::Var, <Self::Solution as Assignment>::Value>,
+  ) -> Result<bool, Self::ConflictErr> {
+    let _ = domains;
+    Ok(false)
+  }
+}
+
+/// The values that each variable can still take
+pub type Domains<V, T> = HashMap<V, HashSet<T>>;
+
+/// How a search branches on a constraint to find solutions
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Branching {
+  /// Branch on each of [`Constraint::decompositions`]
+  #[default]
+  Decompositions,
+  /// Branch on each value of a single variable, see [`Constraint::split_on`]
+  Values,
+  /// Branch on a single variable taking one value, or any other value
+  Binary,
 }
@@ -0,0 +1,315 @@
+//! Explanations for why variables were assigned whilst solving a system
+
+use std::collections::{HashMap, HashSet};
+use std::hash::{Hash, Hasher};
+use std::rc::Rc;
+
+use crate::{assignment::Assignment, constraint::Constraint, system::System};
+
+/// How a constraint came to be in its current state
+#[derive(Debug)]
+pub enum Derivation<C> {
+  /// A constraint that was added to the system
+  Given(C),
+  /// A constraint that was assumed whilst searching for solutions
+  Assumed(C),
+  /// A constraint that was reduced by another constraint
+  Reduced {
+    /// How the constraint was derived before being reduced
+    from: Rc<Derivation<C>>,
+    /// How the constraint it was reduced by was derived
+    by: Rc<Derivation<C>>,
+    /// The constraint after being reduced
+    result: C,
+  },
+}
+
+impl<C> Derivation<C> {
+  /// The constraint produced by this derivation
+  pub fn constraint(&self) -> &C {
+    match self {
+      Self::Given(cons) | Self::Assumed(cons) => cons,
+      Self::Reduced { result, .. } => result,
+    }
+  }
+
+  /// All given and assumed constraints that this derivation depends on,\
+  /// in the order that they were first used.
+  pub fn premises(&self) -> Vec<&C>
+  where
+    C: PartialEq,
+  {
+    let mut premises = vec![];
+    for premise in self.all_premises() {
+      if !premises.contains(&premise) {
+        premises.push(premise);
+      }
+    }
+    premises
+  }
+
+  /// All premises of this derivation, including repeats
+  fn all_premises(&self) -> Vec<&C> {
+    match self {
+      Self::Given(cons) | Self::Assumed(cons) => vec![cons],
+      Self::Reduced { from, by, .. } => {
+        let mut premises = from.all_premises();
+        premises.extend(by.all_premises());
+        premises
+      }
+    }
+  }
+
+  /// Each reduction made in this derivation, in the order they were made.
+  ///
+  /// ## Returns
+  ///
+  /// A list of `(constraint, reduced_by, result)` triples
+  pub fn reductions(&self) -> Vec<(&C, &C, &C)> {
+    match self {
+      Self::Given(_) | Self::Assumed(_) => vec![],
+      Self::Reduced { from, by, result } => {
+        let mut steps = by.reductions();
+        steps.extend(from.reductions());
+        steps.push((from.constraint(), by.constraint(), result));
+        steps
+      }
+    }
+  }
+}
+
+/// A constraint that keeps track of how it was derived.
+///
+/// Solving a system of explained constraints produces an [`Explanation`],\
+/// which records the [`Derivation`] that forced each variable's value.
+///
+/// ## Examples
+///
+/// ```
+/// # use farc3::prelude::*;
+/// # use farc3::explain::Explained;
+/// let clue = MineConstraint::new([0, 1], 1);
+/// let mine = MineConstraint::new([0], 1);
+/// let mut sys = System::from([clue.clone(), mine.clone()]).explained();
+///
+/// // tile 1 is safe, as the clue is already satisfied by the mine at tile 0
+/// let sltn = sys.pop_solution().unwrap();
+/// let reason = sltn.reason(&1).unwrap();
+/// assert_eq!(reason.premises(), vec![&clue, &mine]);
+/// assert_eq!(reason.constraint(), &MineConstraint::new([1], 0));
+/// ```
+#[derive(Debug, Clone)]
+pub struct Explained<C> {
+  constraint: C,
+  derivation: Rc<Derivation<C>>,
+}
+
+impl<C: Clone> Explained<C> {
+  /// Constructs an explained constraint that was given to the system
+  pub fn new(constraint: C) -> Self {
+    Self {
+      derivation: Rc::new(Derivation::Given(constraint.clone())),
+      constraint,
+    }
+  }
+}
+
+impl<C: Clone> From<C> for Explained<C> {
+  fn from(value: C) -> Self {
+    Self::new(value)
+  }
+}
+
+impl<C> Explained<C> {
+  /// The constraint being explained
+  pub fn constraint(&self) -> &C {
+    &self.constraint
+  }
+
+  /// How the constraint came to be in its current state
+  pub fn derivation(&self) -> &Derivation<C> {
+    &self.derivation
+  }
+}
+
+impl<C: Hash> Hash for Explained<C> {
+  fn hash<H: Hasher>(&self, state: &mut H) {
+    self.constraint.hash(state);
+  }
+}
+impl<C: PartialEq> PartialEq for Explained<C> {
+  fn eq(&self, other: &Self) -> bool {
+    self.constraint == other.constraint
+  }
+}
+impl<C: Eq> Eq for Explained<C> {}
+
+impl<C: Constraint + Clone> Constraint for Explained<C>
+where
+  C::Var: Hash + Eq,
+{
+  type Var = C::Var;
+  type Solution = Explanation<C::Var, C::Solution, C>;
+  type ConflictErr = C::ConflictErr;
+
+  fn size(&self) -> usize {
+    self.constraint.size()
+  }
+
+  fn variables(&self) -> impl Iterator<Item = Self::Var> {
+    self.constraint.variables()
+  }
+
+  fn decompositions(&self) -> impl Iterator<Item = Self> {
+    self.constraint.decompositions().map(|constraint| Self {
+      derivation: Rc::new(Derivation::Assumed(constraint.clone())),
+      constraint,
+    })
+  }
+
+  fn reduce(&mut self, other: &Self) -> Result<bool, Self::ConflictErr> {
+    let reduced = self.constraint.reduce(&other.constraint)?;
+    if reduced {
+      self.derivation = Rc::new(Derivation::Reduced {
+        from: self.derivation.clone(),
+        by: other.derivation.clone(),
+        result: self.constraint.clone(),
+      });
+    }
+    Ok(reduced)
+  }
+
+  fn pop_solution(&mut self) -> Option<Self::Solution> {
+    let variables: Vec<_> = self.constraint.variables().collect();
+    let solution = self.constraint.pop_solution()?;
+
+    let remaining: HashSet<_> = self.constraint.variables().collect();
+    let reasons = variables
+      .into_iter()
+      .filter(|var| !remaining.contains(var))
+      .map(|var| (var, self.derivation.clone()))
+      .collect();
+
+    Some(Explanation { solution, reasons })
+  }
+
+  fn is_satisfied(&self, solution: &Self::Solution) -> bool {
+    self.constraint.is_satisfied(solution.solution())
+  }
+
+  fn subsumes(&self, other: &Self) -> bool {
+    self.constraint.subsumes(&other.constraint)
+  }
+}
+
+/// A solution, along with the derivation that forced each assigned variable
+#[derive(Debug, Clone)]
+pub struct Explanation<V, S, C> {
+  solution: S,
+  reasons: HashMap<V, Rc<Derivation<C>>>,
+}
+
+impl<V, S: Default, C> Default for Explanation<V, S, C> {
+  fn default() -> Self {
+    Self {
+      solution: Default::default(),
+      reasons: Default::default(),
+    }
+  }
+}
+
+impl<V: Hash + Eq, S, C> Explanation<V, S, C> {
+  /// The solution being explained
+  pub fn solution(&self) -> &S {
+    &self.solution
+  }
+
+  /// Converts into the solution being explained
+  pub fn into_solution(self) -> S {
+    self.solution
+  }
+
+  /// The derivation of the constraint that forced the value of `var`
+  pub fn reason(&self, var: &V) -> Option<&Derivation<C>> {
+    self.reasons.get(var).map(|reason| &**reason)
+  }
+
+  /// The derivations that forced the value of each variable
+  pub fn reasons(&self) -> impl Iterator<Item = (&V, &Derivation<C>)> {
+    self.reasons.iter().map(|(var, reason)| (var, &**reason))
+  }
+}
+
+impl<V: Hash + Eq, S: Assignment<Var = V>, C> Explanation<V, S, C> {
+  /// Forgets the reasons for variables that are no longer assigned
+  fn prune(mut self) -> Self {
+    let solution = &self.solution;
+    self.reasons.retain(|var, _| solution.contains(var));
+    self
+  }
+}
+
+impl<V: Hash + Eq, S: Assignment<Var = V>, C> Assignment for Explanation<V, S, C> {
+  type Var = V;
+  type Value = S::Value;
+  type Conflict = S::Conflict;
+
+  fn get(&self, var: &V) -> Option<&S::Value> {
+    self.solution.get(var)
+  }
+
+  fn len(&self) -> usize {
+    self.solution.len()
+  }
+
+  fn iter(&self) -> impl Iterator<Item = (&V, &S::Value)> {
+    self.solution.iter()
+  }
+
+  /// Assigns a value to a variable, forgetting any reason for its previous value
+  fn insert(&mut self, var: V, value: S::Value) -> Option<S::Value> {
+    self.reasons.remove(&var);
+    self.solution.insert(var, value)
+  }
+
+  fn restrict_to(mut self, vars: impl IntoIterator<Item = V>) -> Self {
+    self.solution = self.solution.restrict_to(vars);
+    self.prune()
+  }
+
+  fn intersection(mut self, other: Self) -> Self {
+    self.solution = self.solution.intersection(other.solution);
+    self.prune()
+  }
+
+  fn union(mut self, other: Self) -> Self {
+    self.solution = self.solution.union(other.solution);
+    for (var, reason) in other.reasons {
+      self.reasons.entry(var).or_insert(reason);
+    }
+    self.prune()
+  }
+
+  fn checked_union(mut self, other: Self) -> Result<Self, Vec<Self::Conflict>> {
+    self.solution = self.solution.checked_union(other.solution)?;
+    for (var, reason) in other.reasons {
+      self.reasons.entry(var).or_insert(reason);
+    }
+    Ok(self)
+  }
+}
+
+impl<C: Constraint + Clone> System<C> {
+  /// Converts into a system that explains why each variable is assigned.
+  ///
+  /// ## See also
+  ///
+  /// - [`Explained`] for how constraints are explained
+  pub fn explained(self) -> System<Explained<C>>
+  where
+    C: Hash + Eq,
+    C::Var: Hash + Eq,
+  {
+    self.into_iter().map(Explained::new).collect()
+  }
+}
@@ -1,6 +1,10 @@
 //! Traits for informing which constraints to explore first in a search
 
-use super::constraint::Constraint;
+use std::cmp::Reverse;
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use super::{assignment::Assignment, constraint::Constraint};
 
 /// A heuristic that guides which constraints to explore first\
 /// whilst searching for solutions to systems of constraints.
@@ -19,6 +23,47 @@ pub trait Heuristic<C> {
   ///
   /// An orderable ranking for the given constraint
   fn rank(&mut self, constraint: &C, overlaps: &[&C]) -> Self::Rank;
+
+  /// Picks the variable to branch on in the best constraint,\
+  /// when branching on the values of variables.
+  ///
+  /// ## Arguments
+  ///
+  /// - `constraint`: the best constraint to explore
+  /// - `overlaps`: all constraints that share the same variables as `constraint`
+  ///
+  /// ## Returns
+  ///
+  /// The variable in the most overlapping constraints, by default
+  fn variable(&mut self, constraint: &C, overlaps: &[&C]) -> Option<C::Var>
+  where
+    C: Constraint,
+    C::Var: Hash + Eq,
+  {
+    constraint.variables().max_by_key(|var| {
+      overlaps
+        .iter()
+        .filter(|other| other.variables().any(|other| &other == var))
+        .count()
+    })
+  }
+
+  /// Reorders the branches on `var` into the order they should be explored in.
+  ///
+  /// ## Arguments
+  ///
+  /// - `var`: the variable that was branched on
+  /// - `branches`: constraints that each assign a single value to `var`
+  ///
+  /// ## Returns
+  ///
+  /// Nothing, branches are left in their original order by default
+  fn order(&mut self, var: &C::Var, branches: &mut [C])
+  where
+    C: Constraint,
+  {
+    let _ = (var, branches);
+  }
 }
 
 impl<C, R: Ord, H: FnMut(&C, &[&C]) -> R> Heuristic<C> for H {
@@ -42,3 +87,76 @@ impl<C: Constraint> Heuristic<C> for DefaultHeuristic {
     (-(constraint.size() as isize), (overlaps.len() as isize))
   }
 }
+
+/// A heuristic that explores solutions in lexicographic order.\
+/// This branches on the earliest variable in a given order,\
+/// exploring its values in a given order.
+///
+/// Variables and values missing from their orders are explored last.
+///
+/// ## See also
+///
+/// - [`System::solve_ordered`](crate::system::System::solve_ordered) for solving with this heuristic
+pub struct Lexicographic<V, T> {
+  /// The position of each variable in the order
+  vars: HashMap<V, usize>,
+  /// The order to explore values in
+  values: Vec<T>,
+}
+
+impl<V: Hash + Eq, T> Lexicographic<V, T> {
+  /// Creates a lexicographic heuristic from a variable order and a value order
+  pub fn new(vars: impl IntoIterator<Item = V>, values: impl IntoIterator<Item = T>) -> Self {
+    let mut order = HashMap::new();
+    for var in vars {
+      let idx = order.len();
+      order.entry(var).or_insert(idx);
+    }
+
+    Self {
+      vars: order,
+      values: values.into_iter().collect(),
+    }
+  }
+
+  /// The position of `var` in the variable order, if it's present
+  fn position(&self, var: &V) -> Option<usize> {
+    self.vars.get(var).copied()
+  }
+}
+
+impl<C, V, T> Heuristic<C> for Lexicographic<V, T>
+where
+  C: Constraint<Var = V> + Clone,
+  C::Solution: Assignment<Value = T>,
+  V: Hash + Eq,
+  T: PartialEq,
+{
+  type Rank = Reverse<usize>;
+
+  fn rank(&mut self, constraint: &C, _overlaps: &[&C]) -> Self::Rank {
+    let first = constraint
+      .variables()
+      .filter_map(|var| self.position(&var))
+      .min();
+    Reverse(first.unwrap_or(usize::MAX))
+  }
+
+  fn variable(&mut self, constraint: &C, _overlaps: &[&C]) -> Option<C::Var> {
+    constraint
+      .variables()
+      .filter_map(|var| Some((self.position(&var)?, var)))
+      .min_by_key(|(idx, _)| *idx)
+      .map(|(_, var)| var)
+  }
+
+  fn order(&mut self, var: &C::Var, branches: &mut [C]) {
+    branches.sort_by_cached_key(|branch| {
+      let solution = branch.clone().pop_solution();
+      let value = solution.as_ref().and_then(|solution| solution.get(var));
+      value
+        .and_then(|value| self.values.iter().position(|other| other == value))
+        .unwrap_or(usize::MAX)
+    });
+  }
+}
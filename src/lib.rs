@@ -29,15 +29,20 @@
 //! There's also some common variants of constraints:
 //!
 //! - [`DiscreteConstraint`] that covers most forms of discrete constraints
+//! - [`CompactConstraint`] that covers the same constraints, using compact tables
 //! - [`MineConstraint`] that can be used for minesweeper mine solving
+//! - [`ParityConstraint`] that can be used for Lights Out and other XOR puzzles
 //!
 //! [`DiscreteConstraint`]: crate::systems::generic::constraint::DiscreteConstraint
+//! [`CompactConstraint`]: crate::systems::generic::compact::CompactConstraint
 //! [`MineConstraint`]: crate::systems::mines::constraint::MineConstraint
+//! [`ParityConstraint`]: crate::systems::parity::constraint::ParityConstraint
 //!
 //! # Examples
 //!
 //! ```
 //! use farc3::prelude::*;
+//! # use farc3::systems::mines::assignment::MineAssignment;
 //!
 //! // Construct the two mine constraints:
 //! // 1. 2 mines among tiles 0, 1 and 2
@@ -46,28 +51,25 @@
 //! let constraint_1 = MineConstraint::new([1, 2], 1);
 //!
 //! // Construct the constraint system from these 2 constraints
-//! let mut sys = System::from([
+//! let sys = System::from([
 //!   constraint_0,
 //!   constraint_1
 //! ]);
 //!
 //! // Find all solutions to the system
-//! let sltns: HashSet<_> = sys.solve().collect();
+//! let sltns: Vec<_> = sys.solve().collect();
 //!
 //! // All solutions should mark tile 0 as a mine
 //! // as only one of 1 and 2 can be a mine
-//! assert_eq!(
-//!   sltns,
-//!   HashSet::from([
-//!     MineAssignment::new(/*safe*/ [1], /*mines*/ [2, 0]),
-//!     MineAssignment::new(/*safe*/ [2], /*mines*/ [1, 0]),
-//!   ])
-//! );
+//! assert_eq!(sltns.len(), 2);
+//! assert!(sltns.contains(&MineAssignment::new(/*safe*/ [1], /*mines*/ [2, 0])));
+//! assert!(sltns.contains(&MineAssignment::new(/*safe*/ [2], /*mines*/ [1, 0])));
 //! ```
 #![warn(missing_docs)]
 
 pub mod assignment;
 pub mod constraint;
+pub mod explain;
 pub mod heuristics;
 pub mod system;
 pub mod systems;
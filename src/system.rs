@@ -1,14 +1,15 @@
 //! A generic constraint solving algorithm for a system of constraints
 
-use std::collections::{BTreeSet, HashMap, HashSet};
+use std::collections::{BTreeSet, HashMap, HashSet, VecDeque, hash_map::Entry};
+use std::error::Error;
+use std::fmt::{self, Debug, Display};
 use std::hash::{DefaultHasher, Hash, Hasher};
-use std::mem::{self, MaybeUninit};
 use std::{slice, vec};
 
 use crate::{
   assignment::Assignment,
-  constraint::Constraint,
-  heuristics::{DefaultHeuristic, Heuristic},
+  constraint::{Branching, Constraint, Domains},
+  heuristics::{DefaultHeuristic, Heuristic, Lexicographic},
 };
 
 /// A Generic constraint system.
@@ -22,8 +23,9 @@ use crate::{
 ///
 /// 1. There are no duplicate constraints in [`Self::constraints`]
 /// 2. For each variable that a constraint affects, there's a back reference in [`Self::references`]
+/// 3. Each constraint has been restricted to [`Self::domains`] once it's been minimised
+/// 4. Each constraint's hash in [`Self::hashes`] is at the same index as it is in [`Self::constraints`]
 ///
-#[derive(Clone, Debug)]
 pub struct System<C: Constraint> {
   /// Constraints to be solved.
   ///
@@ -43,10 +45,80 @@ pub struct System<C: Constraint> {
   /// This is used make constraint removal simpler, avoid duplicates\
   /// and "hide" the fact we're using a `Vec` under the hood
   idx_map: HashMap<u64, usize>,
+  /// The hash each constraint is indexed under in `idx_map`, in the order of `constraints`
+  hashes: Vec<u64>,
   /// Back-references to aid in solving this constraint system
   references: HashMap<C::Var, HashSet<usize>>,
   /// Constraints to start minimisation from
   to_minimise: BTreeSet<usize>,
+  /// The values that each variable can still take, shared between all constraints that affect it.\
+  /// Variables are only given a domain by constraints that track their values,\
+  /// see [`Constraint::supports`].
+  domains: Domains<C::Var, <C::Solution as Assignment>::Value>,
+  /// How strongly constraints are propagated before popping solutions
+  propagation: Propagation,
+  /// How constraints are branched on whilst searching for solutions
+  branching: Branching,
+}
+
+// `Clone` and `Debug` are implemented by hand,
+// as deriving them doesn't bound the values of solutions
+impl<C: Constraint + Clone> Clone for System<C>
+where
+  C::Var: Clone,
+{
+  fn clone(&self) -> Self {
+    Self {
+      constraints: self.constraints.clone(),
+      idx_map: self.idx_map.clone(),
+      hashes: self.hashes.clone(),
+      references: self.references.clone(),
+      to_minimise: self.to_minimise.clone(),
+      domains: self.domains.clone(),
+      propagation: self.propagation,
+      branching: self.branching,
+    }
+  }
+
+  fn clone_from(&mut self, source: &Self) {
+    self.constraints.clone_from(&source.constraints);
+    self.idx_map.clone_from(&source.idx_map);
+    self.hashes.clone_from(&source.hashes);
+    self.references.clone_from(&source.references);
+    self.to_minimise.clone_from(&source.to_minimise);
+    self.domains.clone_from(&source.domains);
+    self.propagation = source.propagation;
+    self.branching = source.branching;
+  }
+}
+
+impl<C: Constraint + Debug> Debug for System<C>
+where
+  C::Var: Debug,
+  <C::Solution as Assignment>::Value: Debug,
+{
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.debug_struct("System")
+      .field("constraints", &self.constraints)
+      .field("idx_map", &self.idx_map)
+      .field("hashes", &self.hashes)
+      .field("references", &self.references)
+      .field("to_minimise", &self.to_minimise)
+      .field("domains", &self.domains)
+      .field("propagation", &self.propagation)
+      .field("branching", &self.branching)
+      .finish()
+  }
+}
+
+/// How strongly a [`System`] propagates constraints before popping solutions
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Propagation {
+  /// Only reduce overlapping pairs of constraints, see [`System::minimise`]
+  #[default]
+  Pairwise,
+  /// Also discard decompositions that lead to conflicts, see [`System::probe`]
+  Probing,
 }
 
 /*------------------------------------------------
@@ -57,8 +129,12 @@ impl<C: Constraint> Default for System<C> {
     Self {
       constraints: Default::default(),
       idx_map: Default::default(),
+      hashes: Default::default(),
       references: Default::default(),
       to_minimise: Default::default(),
+      domains: Default::default(),
+      propagation: Default::default(),
+      branching: Default::default(),
     }
   }
 }
@@ -73,9 +149,11 @@ where
     let len = self.constraints.len();
     let range = len..len + constraints.len();
 
-    self.idx_map.extend(
-      constraints.iter().map(default_hash).zip(range.clone()), //
-    );
+    let hashes: Vec<_> = constraints.iter().map(default_hash).collect();
+    self
+      .idx_map
+      .extend(hashes.iter().copied().zip(range.clone()));
+    self.hashes.extend(hashes);
 
     for (idx, cons) in range.clone().zip(&constraints) {
       for var in cons.variables() {
@@ -83,8 +161,11 @@ where
       }
     }
 
-    self.to_minimise.extend(range);
+    self.to_minimise.extend(range.clone());
     self.constraints.extend(constraints);
+    for idx in range {
+      self.narrow(idx);
+    }
   }
 }
 
@@ -128,6 +209,11 @@ impl<C: Constraint> System<C> {
   pub fn len(&self) -> usize {
     self.constraints.len()
   }
+
+  /// An iterator over the constraints currently in the system
+  pub fn iter(&self) -> slice::Iter<'_, C> {
+    self.constraints.iter()
+  }
 }
 
 impl<C: Constraint> IntoIterator for System<C> {
@@ -176,13 +262,60 @@ impl<C: Constraint> System<C> {
 
     // log that we've seen the constraint
     self.idx_map.insert(hash, idx);
+    self.hashes.push(hash);
 
     // add constraint
     self.constraints.push(constraint);
     self.to_minimise.insert(idx);
+    self.narrow(idx);
     false
   }
 
+  /// Narrows the shared domains to the values supported by the constraint at `idx`,\
+  /// queueing every other constraint affecting a narrowed variable to be minimised.
+  fn narrow(&mut self, idx: usize)
+  where
+    C::Var: Hash + Eq,
+  {
+    for (var, values) in self.constraints[idx].supports() {
+      let others: Vec<_> = self
+        .references
+        .get(&var)
+        .into_iter()
+        .flatten()
+        .filter(|&&other| other != idx)
+        .copied()
+        .collect();
+
+      let narrowed = match self.domains.entry(var) {
+        Entry::Vacant(entry) => {
+          entry.insert(values);
+          true
+        }
+        Entry::Occupied(mut entry) => {
+          let len = entry.get().len();
+          entry.get_mut().retain(|value| values.contains(value));
+          entry.get().len() < len
+        }
+      };
+      if narrowed {
+        self.to_minimise.extend(others);
+      }
+    }
+  }
+
+  /// Whether `self` contains a given constraint
+  pub fn contains(&self, constraint: &C) -> bool
+  where
+    C: Hash + Eq,
+  {
+    let hash = default_hash(constraint);
+    self
+      .idx_map
+      .get(&hash)
+      .is_some_and(|&idx| &self.constraints[idx] == constraint)
+  }
+
   /// Removes the constraint at a given index from the system and returns it
   fn remove_idx(&mut self, idx: usize) -> Option<C>
   where
@@ -202,6 +335,23 @@ impl<C: Constraint> System<C> {
       idxs.remove(&last_idx);
     }
 
+    // forget the removed constraint and point the last constraint at its new index
+    let hash = self.hashes.swap_remove(idx);
+    if self.idx_map.get(&hash) == Some(&idx) {
+      self.idx_map.remove(&hash);
+    }
+    self.to_minimise.remove(&idx);
+    if idx != last_idx {
+      if let Some(other) = self.idx_map.get_mut(&self.hashes[idx])
+        && *other == last_idx
+      {
+        *other = idx;
+      }
+      if self.to_minimise.remove(&last_idx) {
+        self.to_minimise.insert(idx);
+      }
+    }
+
     // if constraint happens to be at end,
     // we don't need to swap remove and can just `pop` instead
     if idx == last_idx {
@@ -242,7 +392,38 @@ impl<C: Constraint> System<C> {
   {
     let hash = default_hash(constraint);
     let &idx = self.idx_map.get(&hash)?;
-    self.remove_idx(idx)
+    let removed = self.remove_idx(idx);
+
+    // the removed constraint may have narrowed domains, so they're rebuilt from those left
+    self.domains.clear();
+    for idx in 0..self.constraints.len() {
+      self.narrow(idx);
+    }
+    removed
+  }
+
+  /// Sets how strongly constraints are propagated before popping solutions
+  pub fn with_propagation(mut self, propagation: Propagation) -> Self {
+    self.propagation = propagation;
+    self
+  }
+
+  /// How strongly constraints are propagated before popping solutions
+  pub fn propagation(&self) -> Propagation {
+    self.propagation
+  }
+
+  /// Sets how constraints are branched on whilst searching for solutions.\
+  /// Branching on values is used for constraints that can be split on variables,\
+  /// falling back to decompositions otherwise.
+  pub fn with_branching(mut self, branching: Branching) -> Self {
+    self.branching = branching;
+    self
+  }
+
+  /// How constraints are branched on whilst searching for solutions
+  pub fn branching(&self) -> Branching {
+    self.branching
   }
 
   /// Queues all constraints to be minimised.\
@@ -254,11 +435,148 @@ impl<C: Constraint> System<C> {
   }
 }
 
+/*------------------------------------------------
+-              Verification methods              -
+------------------------------------------------*/
+impl<C: Constraint> System<C> {
+  /// Checks whether an assignment solves this system.\
+  /// The assignment may come from solving `self`, or from an external solver.
+  ///
+  /// Constraints are removed from the system as they're solved,\
+  /// so solutions should be verified against a system that hasn't been solved yet.
+  ///
+  /// Partly assigned constraints are violated when no assignment to their other variables satisfies them.\
+  /// This is only checked for constraints that can be split on their variables, see [`Constraint::split_on`].
+  ///
+  /// ## Arguments
+  ///
+  /// - `solution`: the assignment to check
+  ///
+  /// ## Returns
+  ///
+  /// Every violated constraint and unassigned variable, if there are any
+  ///
+  /// ## Examples
+  ///
+  /// ```
+  /// # use farc3::prelude::*;
+  /// # use farc3::systems::mines::assignment::MineAssignment;
+  /// let sys = System::from([
+  ///   MineConstraint::new([0, 1, 2], 2),
+  ///   MineConstraint::new([1, 2], 1),
+  /// ]);
+  ///
+  /// assert!(sys.verify(&MineAssignment::new([1], [0, 2])).is_ok());
+  ///
+  /// let violations = sys.verify(&MineAssignment::new([0], [1])).unwrap_err();
+  /// assert!(violations.violated.is_empty());
+  /// assert_eq!(violations.unassigned, [2].into());
+  ///
+  /// let violations = sys.verify(&MineAssignment::new([], [0, 1, 2])).unwrap_err();
+  /// assert_eq!(violations.violated.len(), 2);
+  /// ```
+  pub fn verify(&self, solution: &C::Solution) -> Result<(), Violations<'_, C>>
+  where
+    C: Clone,
+    C::Var: Hash + Eq,
+    <C::Solution as Assignment>::Value: PartialEq,
+  {
+    let mut violations = Violations {
+      violated: vec![],
+      unassigned: HashSet::new(),
+    };
+
+    for constraint in &self.constraints {
+      let unassigned: Vec<_> = constraint
+        .variables()
+        .filter(|var| !solution.contains(var))
+        .collect();
+
+      let violated = match unassigned.is_empty() {
+        true => !constraint.is_satisfied(solution),
+        false => !extends(constraint, solution),
+      };
+      if violated {
+        violations.violated.push(constraint);
+      }
+      violations.unassigned.extend(unassigned);
+    }
+
+    if violations.violated.is_empty() && violations.unassigned.is_empty() {
+      return Ok(());
+    }
+    Err(violations)
+  }
+}
+
+/// Whether the variables of `constraint` assigned by `solution`\
+/// can be extended into an assignment that satisfies `constraint`.
+///
+/// Each assigned variable is split on and reduced by, so that conflicts show up as failed reductions.\
+/// Constraints that can't be split on their variables are assumed to be extendable.
+fn extends<C: Constraint + Clone>(constraint: &C, solution: &C::Solution) -> bool
+where
+  <C::Solution as Assignment>::Value: PartialEq,
+{
+  let mut constraint = constraint.clone();
+  let assigned: Vec<_> = constraint
+    .variables()
+    .filter(|var| solution.contains(var))
+    .collect();
+
+  for var in assigned {
+    let (Some(value), Some(splits)) = (solution.get(&var), constraint.split_on(&var)) else {
+      continue;
+    };
+    let split = splits.into_iter().find(|split| {
+      let sltn = split.clone().pop_solution();
+      sltn.is_some_and(|sltn| sltn.get(&var) == Some(value))
+    });
+    let Some(split) = split else {
+      return false;
+    };
+    if constraint.reduce(&split).is_err() {
+      return false;
+    }
+  }
+  true
+}
+
+/// The reasons that an assignment doesn't solve a [`System`],\
+/// as found by [`System::verify`].
+#[derive(Debug, Clone)]
+pub struct Violations<'a, C: Constraint> {
+  /// Constraints with all variables assigned, to values that they don't allow
+  pub violated: Vec<&'a C>,
+  /// Variables in the system that the assignment doesn't assign
+  pub unassigned: HashSet<C::Var>,
+}
+
+impl<C: Constraint + Debug> Display for Violations<'_, C>
+where
+  C::Var: Debug,
+{
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(
+      f,
+      "assignment violates {:?} and leaves {:?} unassigned",
+      self.violated, self.unassigned
+    )
+  }
+}
+
+impl<C: Constraint + Debug> Error for Violations<'_, C> where C::Var: Debug {}
+
 /*------------------------------------------------
 -             System solving methods             -
 ------------------------------------------------*/
 impl<C: Constraint> System<C> {
-  /// Pops the solution for all decided variables in `self`.
+  /// Pops the solution for all decided variables in `self`.\
+  /// Constraints are only minimised pairwise, whatever the [`Propagation`] of `self`.
+  ///
+  /// ## See also
+  ///
+  /// - [`System::pop_propagated_solution`] for propagating as set by [`System::with_propagation`]
   pub fn pop_solution(&mut self) -> Result<C::Solution, C::ConflictErr>
   where
     C: Hash + Eq,
@@ -310,7 +628,11 @@ impl<C: Constraint> System<C> {
     // short circuit on all constraints empty
     if idxs.len() == self.constraints.len() {
       self.constraints.clear();
+      self.idx_map.clear();
+      self.hashes.clear();
       self.references.clear();
+      self.to_minimise.clear();
+      return;
     }
 
     // remove constraints in reverse order
@@ -330,14 +652,22 @@ impl<C: Constraint> System<C> {
   where
     C::Var: Hash + Eq,
   {
-    // We need to get around the borrow checker hating holding references
-    // to different items within slices / `Vec`s.
-    // To do this we can create a placeholder item for "taking" items from `self.constraints`
-    // invariant 1: `placeholder`` is **not** left in `self.constraints`
-    // invariant 2: no methods of `placeholder` are called
-    let mut placeholder: C = unsafe { MaybeUninit::zeroed().assume_init() };
-
     while let Some(idx) = self.to_minimise.pop_first() {
+      // restrict the constraint to the shared domains before reducing others by it,
+      // restricting can remove variables, so references are rebuilt afterwards
+      for var in self.constraints[idx].variables() {
+        if let Some(idxs) = self.references.get_mut(&var) {
+          idxs.remove(&idx);
+        }
+      }
+      let restricted = self.constraints[idx].restrict(&self.domains);
+      for var in self.constraints[idx].variables() {
+        self.references.entry(var).or_default().insert(idx);
+      }
+      if restricted? {
+        self.narrow(idx);
+      }
+
       let overlaps = self.overlaps_at(idx);
 
       // delete overlapping constraints from references before updating
@@ -352,19 +682,23 @@ impl<C: Constraint> System<C> {
       }
 
       // reduce all overlapping constraints with the constraint at `idx`
-      let constraint = mem::replace(&mut self.constraints[idx], placeholder);
       let reduced: Vec<_> = overlaps
         .iter()
         .filter_map(|&overlap| {
-          // invariant 2 is maintained here as overlaps does not contain `idx`
-          self.constraints[overlap]
-            .reduce(&constraint)
+          // overlaps does not contain `idx`, so we can split the borrow around it
+          let (constraint, other) = if overlap < idx {
+            let (left, right) = self.constraints.split_at_mut(idx);
+            (&mut left[overlap], &right[0])
+          } else {
+            let (left, right) = self.constraints.split_at_mut(overlap);
+            (&mut right[0], &left[idx])
+          };
+          constraint
+            .reduce(other)
             .map(|reduced| reduced.then_some(overlap))
             .transpose()
         })
         .collect::<Result<_, _>>()?;
-      // maintain invariant 1, remove placeholder from `self.constraints`
-      placeholder = mem::replace(&mut self.constraints[idx], constraint);
 
       // re-add overlapping constraints to references after update
       for &idx in &overlaps {
@@ -377,14 +711,120 @@ impl<C: Constraint> System<C> {
         }
       }
 
-      // add any constraints successfully reduced to minimise from
+      // add any constraints successfully reduced to minimise from,
+      // sharing the values they no longer support with all other constraints
+      for &idx in &reduced {
+        self.narrow(idx);
+      }
       self.to_minimise.extend(reduced);
     }
 
     Ok(self)
   }
 
-  /// Returns the best constraint to explore, according to a given heuristic
+  /// Removes constraints that are subsumed by another constraint in the system.\
+  /// Of several equivalent constraints, only the last one is kept.
+  ///
+  /// ## Returns
+  ///
+  /// The constraints that were removed
+  ///
+  /// ## Examples
+  ///
+  /// ```
+  /// # use farc3::prelude::*;
+  /// let mut sys = System::from([
+  ///   MineConstraint::new([0, 1, 2], 0),
+  ///   MineConstraint::new([1, 2], 0),
+  ///   MineConstraint::new([2, 3], 1),
+  /// ]);
+  ///
+  /// let removed = sys.simplify();
+  /// assert_eq!(removed, vec![MineConstraint::new([1, 2], 0)]);
+  /// assert_eq!(sys.len(), 2);
+  /// ```
+  ///
+  /// ## See also
+  ///
+  /// - [`Constraint::subsumes`] for when one constraint subsumes another
+  pub fn simplify(&mut self) -> Vec<C>
+  where
+    C: Hash + Eq,
+    C::Var: Hash + Eq,
+  {
+    let mut subsumed = BTreeSet::new();
+    for idx in 0..self.constraints.len() {
+      let constraint = &self.constraints[idx];
+      let is_subsumed = self
+        .overlaps_at(idx)
+        .into_iter()
+        .filter(|other| !subsumed.contains(other))
+        .any(|other| self.constraints[other].subsumes(constraint));
+      if is_subsumed {
+        subsumed.insert(idx);
+      }
+    }
+
+    // remove constraints in reverse order
+    // this avoids swapping constraints that'll be removed
+    let mut removed: Vec<_> = subsumed
+      .into_iter()
+      .rev()
+      .filter_map(|idx| self.remove_idx(idx))
+      .collect();
+    removed.reverse();
+    removed
+  }
+
+  /// Makes the system generalised arc consistent from scratch,\
+  /// pruning values that have no support from the domains of variables.
+  ///
+  /// Domains are shared between all constraints that affect a variable,\
+  /// and kept arc consistent as constraints are inserted and minimised (AC-3).\
+  /// This re-minimises every constraint, for when the system has been changed in other ways.
+  ///
+  /// ## Returns
+  ///
+  /// A mutable reference to allow method chaining
+  ///
+  /// ## Examples
+  ///
+  /// ```
+  /// # use std::collections::{HashMap, HashSet};
+  /// # use farc3::prelude::*;
+  /// // x < y and y < z, for x, y, z in 0..3
+  /// let less = |a, b| {
+  ///   DiscreteConstraint::from_iter(
+  ///     (0..3).flat_map(|i| (i + 1..3).map(move |j| [(a, i), (b, j)])),
+  ///   )
+  /// };
+  /// let mut sys = System::from([less("x", "y"), less("y", "z")]);
+  ///
+  /// let domains = sys.arc_consistency().unwrap().domains();
+  /// assert_eq!(domains, &HashMap::from([
+  ///   ("x", HashSet::from([0])),
+  ///   ("y", HashSet::from([1])),
+  ///   ("z", HashSet::from([2])),
+  /// ]));
+  /// ```
+  pub fn arc_consistency(&mut self) -> Result<&mut Self, C::ConflictErr>
+  where
+    C::Var: Hash + Eq,
+  {
+    self.domains.clear();
+    for idx in 0..self.constraints.len() {
+      self.narrow(idx);
+    }
+    self.queue_all().minimise()
+  }
+
+  /// The values that each variable can still take,\
+  /// for variables affected by constraints that track their values
+  pub fn domains(&self) -> &Domains<C::Var, <C::Solution as Assignment>::Value> {
+    &self.domains
+  }
+
+  /// Returns the branches to explore from the best constraint, according to a given heuristic
   ///
   /// ## Arguments
   ///
@@ -393,12 +833,12 @@ impl<C: Constraint> System<C> {
   ///
   /// ## Returns
   ///
-  /// The best constraint to explore
-  pub(self) fn best_constraint<H: Heuristic<C>>(&self, heuristic: &mut H) -> Option<&C>
+  /// Constraints that partition the solutions of the best constraint
+  pub(self) fn branches<H: Heuristic<C>>(&self, heuristic: &mut H) -> Option<Vec<C>>
   where
     C::Var: Hash + Eq,
   {
-    self
+    let (constraint, overlaps) = self
       .constraints
       .iter()
       .enumerate()
@@ -412,8 +852,28 @@ impl<C: Constraint> System<C> {
             .collect::<Vec<_>>(),
         )
       })
-      .max_by_key(|(cons, overlaps)| heuristic.rank(cons, overlaps))
-      .map(|(cons, _score)| cons)
+      .max_by_key(|(cons, overlaps)| heuristic.rank(cons, overlaps))?;
+
+    if self.branching == Branching::Decompositions {
+      return Some(constraint.decompositions().collect());
+    }
+    let splits = heuristic
+      .variable(constraint, &overlaps)
+      .and_then(|var| Some((constraint.split_on(&var)?, var)));
+    let Some((mut splits, var)) = splits else {
+      return Some(constraint.decompositions().collect());
+    };
+    heuristic.order(&var, &mut splits);
+
+    // branch on the first value, or any of the others
+    if self.branching == Branching::Binary
+      && splits.len() > 2
+      && let Some(rest) = constraint.exclude(&splits[0])
+    {
+      splits.truncate(1);
+      splits.push(rest);
+    }
+    Some(splits)
   }
 
   /// Finds the indexes of constraints that overlap the constraint at `idx`
@@ -431,6 +891,16 @@ impl<C: Constraint> System<C> {
     overlaps
   }
 
+  /// Hashes the constraints of this system, independent of the order they were added in
+  fn canonical_hash(&self) -> u64
+  where
+    C: Hash + Eq,
+  {
+    let mut hashes: Vec<_> = self.constraints.iter().map(default_hash).collect();
+    hashes.sort_unstable();
+    default_hash(hashes)
+  }
+
   /// Returns all solutions to this system of equations,\
   /// using the default heuristic
   ///
@@ -467,22 +937,16 @@ impl<C: Constraint> System<C> {
   ///
   /// - [`System::solve`] for using the default heuristic
   /// - [`System::solve_with_default`] for providing a heuristic type
-  pub fn solve_with<H>(mut self, heuristic: H) -> SystemIter<C, H>
+  pub fn solve_with<H>(self, heuristic: H) -> SystemIter<C, H>
   where
     C: Hash + Eq + Clone,
     C::Var: Hash + Eq,
     C::Solution: Default,
   {
-    let Ok(solution) = self.pop_solution() else {
-      return SystemIter {
-        stack: vec![],
-        heuristic,
-      };
-    };
-
     SystemIter {
-      stack: vec![(self, solution)],
+      stack: vec![Frame::Start(Box::new(self))],
       heuristic,
+      transpositions: None,
     }
   }
 
@@ -505,54 +969,453 @@ impl<C: Constraint> System<C> {
   {
     self.solve_with(Default::default())
   }
+
+  /// Returns all solutions to this system of equations in lexicographic order,\
+  /// comparing solutions by the first variable in `vars` that they differ on.
+  ///
+  /// Solutions that only differ on variables missing from `vars` are returned in no particular order.
+  ///
+  /// ## Note
+  ///
+  /// Ordering relies on splitting constraints on their variables, see [`Constraint::split_on`].\
+  /// Constraints that can't be split are branched on by their decompositions instead,\
+  /// so solutions are only ordered by the variables of constraints that can be split.
+  ///
+  /// ## Arguments
+  ///
+  /// - `vars`: the order to compare variables in
+  /// - `values`: the order to compare values in, missing values are ordered last
+  ///
+  /// ## Returns
+  ///
+  /// An iterator over possible solutions to the [`System`], in lexicographic order
+  ///
+  /// ## Examples
+  ///
+  /// ```
+  /// use farc3::prelude::*;
+  /// # use farc3::systems::mines::assignment::MineAssignment;
+  ///
+  /// let sys = System::from([MineConstraint::new([0, 1, 2], 2)]);
+  ///
+  /// let sltns: Vec<_> = sys.solve_ordered([0, 1, 2], [false, true]).collect();
+  /// assert_eq!(sltns[0], MineAssignment::from_iter([(0, false), (1, true), (2, true)]));
+  /// assert_eq!(sltns[2], MineAssignment::from_iter([(0, true), (1, true), (2, false)]));
+  /// ```
+  ///
+  /// ## See also
+  ///
+  /// - [`System::kth_solution`] for finding a single solution in this order
+  pub fn solve_ordered<T>(
+    self,
+    vars: impl IntoIterator<Item = C::Var>,
+    values: impl IntoIterator<Item = T>,
+  ) -> SystemIter<C, Lexicographic<C::Var, T>>
+  where
+    C: Hash + Eq + Clone,
+    C::Var: Hash + Eq,
+    C::Solution: Assignment<Value = T> + Default,
+    T: PartialEq,
+  {
+    self
+      .with_branching(Branching::Values)
+      .solve_with(Lexicographic::new(vars, values))
+  }
+
+  /// Finds the `k`-th solution (from 0) to this system of equations, in lexicographic order
+  ///
+  /// ## Arguments
+  ///
+  /// - `vars`: the order to compare variables in
+  /// - `values`: the order to compare values in, missing values are ordered last
+  /// - `k`: the number of solutions to skip
+  ///
+  /// ## Returns
+  ///
+  /// The `k`-th solution, or `None` if there are `k` solutions or fewer
+  ///
+  /// ## Note
+  ///
+  /// The solutions before the `k`-th are all found and skipped,\
+  /// as the number of solutions under each branch isn't known without exploring it.\
+  /// This takes time linear in `k`, on top of the search for each solution.
+  ///
+  /// ## See also
+  ///
+  /// - [`System::solve_ordered`] for the order solutions are in
+  pub fn kth_solution<T>(
+    self,
+    vars: impl IntoIterator<Item = C::Var>,
+    values: impl IntoIterator<Item = T>,
+    k: usize,
+  ) -> Option<C::Solution>
+  where
+    C: Hash + Eq + Clone,
+    C::Var: Hash + Eq + Clone,
+    C::Solution: Assignment<Value = T> + Default + Clone,
+    T: PartialEq,
+  {
+    self.solve_ordered(vars, values).nth(k)
+  }
+}
+
+/*------------------------------------------------
+-             System probing methods             -
+------------------------------------------------*/
+impl<C: Constraint + Hash + Eq + Clone> System<C>
+where
+  C::Var: Hash + Eq + Clone,
+{
+  /// Tentatively inserts each decomposition of each constraint and minimises,\
+  /// permanently discarding decompositions that lead to conflicts.\
+  /// Constraints split on each of their variables are also tried in the same way.
+  ///
+  /// This finds values forced by chains of constraints that [`System::minimise`] misses,\
+  /// at the cost of minimising a copy of the system for every decomposition.
+  ///
+  /// ## Returns
+  ///
+  /// A mutable reference to allow method chaining,\
+  /// or a conflict error if every decomposition of a constraint leads to a conflict.
+  ///
+  /// Decompositions can only be discarded by constraints that implement [`Constraint::exclude`].\
+  /// For constraints where it returns `None` (such as [`Explained`](crate::explain::Explained)),\
+  /// probing discards nothing and only reports constraints whose every decomposition conflicts.
+  ///
+  /// ## See also
+  ///
+  /// - [`Constraint::exclude`] for how decompositions are discarded
+  pub fn probe(&mut self) -> Result<&mut Self, C::ConflictErr> {
+    self.minimise()?;
+
+    // discarding a decomposition can cause others to conflict,
+    // so keep probing until nothing more is discarded
+    // decompositions are tried on a scratch copy, reusing its allocations between them
+    let mut probe = self.clone();
+    let mut discarded = true;
+    while discarded {
+      discarded = false;
+
+      for idx in 0..self.constraints.len() {
+        let mut conflict = None;
+        let mut survived = false;
+        let mut exclusion = None;
+
+        // decompositions only branch on some variables, so also try splitting on each
+        let constraint = &self.constraints[idx];
+        let splits = constraint
+          .variables()
+          .filter_map(|var| constraint.split_on(&var))
+          .flatten();
+        let candidates: HashSet<_> = constraint.decompositions().chain(splits).collect();
+
+        for decomposition in candidates {
+          probe.clone_from(self);
+          probe.insert(decomposition.clone());
+          let Err(err) = probe.minimise() else {
+            survived = true;
+            continue;
+          };
+          conflict = Some(err);
+
+          // re-inserting an existing exclusion wouldn't discard anything
+          exclusion = self.constraints[idx]
+            .exclude(&decomposition)
+            .filter(|exclusion| !self.contains(exclusion));
+          if exclusion.is_some() {
+            break;
+          }
+        }
+
+        if let Some(exclusion) = exclusion {
+          self.insert(exclusion);
+          self.minimise()?;
+          discarded = true;
+          break;
+        }
+        // every candidate has been tried, and all of them conflict
+        if let (false, Some(err)) = (survived, conflict) {
+          return Err(err);
+        }
+      }
+    }
+
+    Ok(self)
+  }
+
+  /// Pops the solution for all decided variables in `self`,\
+  /// propagating constraints as strongly as set by [`System::with_propagation`] first.
+  ///
+  /// ## See also
+  ///
+  /// - [`System::pop_solution`] for only minimising constraints pairwise
+  pub fn pop_propagated_solution(&mut self) -> Result<C::Solution, C::ConflictErr>
+  where
+    C::Solution: Default,
+  {
+    if self.propagation == Propagation::Probing {
+      self.probe()?;
+    }
+    self.pop_solution()
+  }
 }
 
 /// An iterator for all solutions to a given constraint system
 ///
-/// @todo Symmetry breaking\
-/// i.e. if reducing `constraint1` then `constraint2` leads to a contradiction\
-/// don't attempt to reduce `constraint2` then `constraint1`.
-///
 /// @todo Parallelisation\
 /// This'll mostly consist of working out how to split the solution iterator.\
 /// This could be achieved my using a MaxHeap structure that we partition to split.
 pub struct SystemIter<C: Constraint + Clone, H> {
-  /// A stack of system and their current solutions
-  stack: Vec<(System<C>, C::Solution)>,
+  /// A stack of systems left to explore, alongside their current solutions
+  stack: Vec<Frame<C>>,
   /// The heuristic used to decide which constraint to explore
   heuristic: H,
+  /// Solutions to subsystems that have already been explored, if enabled
+  transpositions: Option<Transpositions<C>>,
+}
+
+/// An entry on the stack of a [`SystemIter`]
+enum Frame<C: Constraint> {
+  /// The system to solve, before any solution has been popped from it
+  Start(Box<System<C>>),
+  /// A subsystem to explore and its solution so far
+  Explore(Box<System<C>>, C::Solution),
+  /// The most recently opened subsystem has been fully explored
+  Close,
+}
+
+impl<C: Constraint + Clone, H> SystemIter<C, H> {
+  /// Remembers the solutions to subsystems that have been fully explored,\
+  /// so that subsystems reached again through a different order of branches aren't explored twice.
+  ///
+  /// Subsystems are what remains after popping the solution of a branch,\
+  /// identified by a canonical hash of their constraints and compared in full on a match.\
+  /// Unsatisfiable subsystems are remembered as having no solutions.
+  ///
+  /// ## Arguments
+  ///
+  /// - `capacity`: the maximum number of constraints and solutions to remember,\
+  ///   including those recorded for subsystems still being explored.\
+  ///   Once full, the oldest subsystems are forgotten first,\
+  ///   then the outermost subsystems being explored stop being recorded
+  ///
+  /// ## Returns
+  ///
+  /// The iterator, memoising explored subsystems
+  ///
+  /// ## Examples
+  ///
+  /// ```
+  /// use farc3::prelude::*;
+  ///
+  /// let sys = System::from([
+  ///   MineConstraint::new([0, 1, 2], 1),
+  ///   MineConstraint::new([2, 3, 4], 1),
+  /// ]);
+  ///
+  /// let sltns = sys.clone().solve().with_transpositions(64);
+  /// assert_eq!(sltns.count(), sys.solve().count());
+  /// ```
+  pub fn with_transpositions(mut self, capacity: usize) -> Self {
+    self.transpositions = Some(Transpositions::new(capacity));
+    self
+  }
 }
 
 impl<C: Constraint + Clone, H: Heuristic<C>> Iterator for SystemIter<C, H>
 where
   System<C>: Clone,
   C: Hash + Eq,
-  C::Var: Hash + Eq,
+  C::Var: Hash + Eq + Clone,
   C::Solution: Default + Clone,
 {
   type Item = C::Solution;
   fn next(&mut self) -> Option<Self::Item> {
-    while let Some((system, solution)) = self.stack.pop() {
+    while let Some(frame) = self.stack.pop() {
+      let (system, solution) = match frame {
+        Frame::Start(mut system) => {
+          let Ok(solution) = system.pop_propagated_solution() else {
+            continue;
+          };
+          (system, solution)
+        }
+        Frame::Explore(system, solution) => (system, solution),
+        Frame::Close => {
+          if let Some(transpositions) = &mut self.transpositions {
+            transpositions.close();
+          }
+          continue;
+        }
+      };
+
       // if we've reached a fully resolved solution, return it
       if system.is_empty() {
+        if let Some(transpositions) = &mut self.transpositions {
+          transpositions.record(&solution);
+        }
         return Some(solution);
       }
 
-      // pick the best constraint to decompose and explore it
-      let best = system
-        .best_constraint(&mut self.heuristic)
+      // replay the solutions of subsystems we've already explored,
+      // otherwise start recording the solutions to this subsystem
+      if let Some(transpositions) = &mut self.transpositions {
+        let key = system.canonical_hash();
+        if let Some(sltns) = transpositions.get(key, &system) {
+          for sltn in sltns.iter().rev() {
+            let Ok(new_sol) = solution.clone().checked_union(sltn.clone()) else {
+              continue;
+            };
+            self.stack.push(Frame::Explore(Box::default(), new_sol));
+          }
+          continue;
+        }
+
+        let vars: HashSet<_> = system.iter().flat_map(|cons| cons.variables()).collect();
+        transpositions.open(Subsystem {
+          key,
+          constraints: system.iter().cloned().collect(),
+          vars: vars.into_iter().collect(),
+        });
+        self.stack.push(Frame::Close);
+      }
+
+      // pick the best constraint to branch on and explore it
+      // branches are pushed in reverse, so they're explored in order
+      let branches = system
+        .branches(&mut self.heuristic)
         .expect("A non-empty System should have a best constraint");
-      for decomposition in best.decompositions() {
+      for branch in branches.into_iter().rev() {
         let mut new_sys = system.clone();
-        new_sys.insert(decomposition);
+        new_sys.insert(branch);
 
-        let Ok(new_sol) = new_sys.pop_solution() else {
+        let Ok(new_sol) = new_sys.pop_propagated_solution() else {
+          continue;
+        };
+        // branches that contradict the solution so far have no solutions
+        let Ok(new_sol) = solution.clone().checked_union(new_sol) else {
           continue;
         };
-        self.stack.push((new_sys, solution.clone().union(new_sol)));
+
+        self.stack.push(Frame::Explore(new_sys, new_sol));
       }
     }
 
     None
   }
 }
+
+/// A subsystem whose solutions are memoised
+struct Subsystem<C: Constraint> {
+  /// The canonical hash of the subsystem's constraints
+  key: u64,
+  /// The subsystem's constraints, to tell apart subsystems with the same hash
+  constraints: Vec<C>,
+  /// The variables the subsystem's solutions assign
+  vars: Vec<C::Var>,
+}
+
+/// A subsystem being explored, alongside the solutions recorded for it so far
+type Recording<C> = (Subsystem<C>, Option<Vec<<C as Constraint>::Solution>>);
+
+/// A bounded memo of the solutions to subsystems explored by a [`SystemIter`]
+struct Transpositions<C: Constraint> {
+  /// The constraints and solutions of each explored subsystem, keyed by its canonical hash
+  entries: HashMap<u64, (Vec<C>, Vec<C::Solution>)>,
+  /// The order entries were inserted in, to forget the oldest first
+  order: VecDeque<u64>,
+  /// The number of constraints and solutions in `entries`
+  size: usize,
+  /// The number of constraints and solutions in `open`
+  recorded: usize,
+  /// The maximum number of constraints and solutions to hold across `entries` and `open`
+  capacity: usize,
+  /// Subsystems currently being explored, innermost last.\
+  /// Recordings are dropped, outermost first, once there's too many to hold
+  open: Vec<Recording<C>>,
+}
+
+impl<C: Constraint> Transpositions<C> {
+  /// Creates an empty memo holding at most `capacity` constraints and solutions
+  fn new(capacity: usize) -> Self {
+    Self {
+      entries: HashMap::new(),
+      order: VecDeque::new(),
+      size: 0,
+      recorded: 0,
+      capacity,
+      open: vec![],
+    }
+  }
+}
+
+impl<C: Constraint + Hash + Eq> Transpositions<C>
+where
+  C::Var: Clone,
+  C::Solution: Clone,
+{
+  /// The solutions to a previously explored subsystem,\
+  /// if it has exactly the constraints of `system`
+  fn get(&self, key: u64, system: &System<C>) -> Option<&Vec<C::Solution>> {
+    let (constraints, sltns) = self.entries.get(&key)?;
+    let same =
+      constraints.len() == system.len() && constraints.iter().all(|cons| system.contains(cons));
+    same.then_some(sltns)
+  }
+
+  /// Starts recording the solutions to a subsystem
+  fn open(&mut self, subsystem: Subsystem<C>) {
+    self.recorded += subsystem.constraints.len();
+    self.open.push((subsystem, Some(vec![])));
+    self.shrink();
+  }
+
+  /// Records a solution against every subsystem currently being explored
+  fn record(&mut self, solution: &C::Solution) {
+    for (subsystem, sltns) in &mut self.open {
+      let Some(recorded) = sltns else {
+        continue;
+      };
+      let vars = subsystem.vars.iter().cloned();
+      recorded.push(solution.clone().restrict_to(vars));
+      self.recorded += 1;
+    }
+    self.shrink();
+  }
+
+  /// Finishes recording the innermost subsystem, remembering its solutions
+  fn close(&mut self) {
+    let Some((subsystem, Some(sltns))) = self.open.pop() else {
+      return;
+    };
+    let weight = subsystem.constraints.len() + sltns.len();
+    self.recorded -= weight;
+    if self.entries.contains_key(&subsystem.key) {
+      return;
+    }
+
+    self.size += weight;
+    self.order.push_back(subsystem.key);
+    self
+      .entries
+      .insert(subsystem.key, (subsystem.constraints, sltns));
+  }
+
+  /// Forgets the oldest subsystems, then stops recording the outermost subsystems,\
+  /// until the memo holds at most `capacity` constraints and solutions
+  fn shrink(&mut self) {
+    while self.size + self.recorded > self.capacity {
+      if let Some(oldest) = self.order.pop_front() {
+        if let Some((constraints, sltns)) = self.entries.remove(&oldest) {
+          self.size -= constraints.len() + sltns.len();
+        }
+        continue;
+      }
+
+      let Some((subsystem, sltns)) = self.open.iter_mut().find(|(_, sltns)| sltns.is_some()) else {
+        break;
+      };
+      let len = sltns.take().map_or(0, |sltns| sltns.len());
+      self.recorded -= subsystem.constraints.len() + len;
+    }
+  }
+}
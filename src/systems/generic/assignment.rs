@@ -1,8 +1,9 @@
 //! Generic assignments from variables to discrete values
 
-use std::{collections::HashMap, collections::hash_map, hash::Hash};
+use std::{collections::HashMap, collections::HashSet, collections::hash_map, hash::Hash};
 
 use crate::prelude::Assignment;
+use crate::systems::mines::assignment::MineAssignment;
 
 /// A generic form of discrete assigments to variables.
 ///
@@ -60,6 +61,33 @@ impl<V: Hash + Eq, T, const N: usize> From<[(V, T); N]> for DiscreteAssignment<V
 }
 
 impl<V: Hash + Eq, T: PartialEq> Assignment for DiscreteAssignment<V, T> {
+  type Var = V;
+  type Value = T;
+  /// The contradicting variable, with the values assigned by `self` and `other`
+  type Conflict = (V, T, T);
+
+  fn get(&self, var: &V) -> Option<&T> {
+    self.0.get(var)
+  }
+
+  fn len(&self) -> usize {
+    self.0.len()
+  }
+
+  fn iter(&self) -> impl Iterator<Item = (&V, &T)> {
+    self.0.iter()
+  }
+
+  fn insert(&mut self, var: V, value: T) -> Option<T> {
+    self.0.insert(var, value)
+  }
+
+  fn restrict_to(mut self, vars: impl IntoIterator<Item = V>) -> Self {
+    let vars: HashSet<V> = vars.into_iter().collect();
+    self.0.retain(|var, _| vars.contains(var));
+    self
+  }
+
   fn intersection(mut self, other: Self) -> Self {
     self.0.retain(|var, value| {
       let Some(value1) = other.0.get(var) else {
@@ -83,6 +111,23 @@ impl<V: Hash + Eq, T: PartialEq> Assignment for DiscreteAssignment<V, T> {
     }
     self
   }
+
+  fn checked_union(mut self, other: Self) -> Result<Self, Vec<Self::Conflict>> {
+    let mut conflicts = vec![];
+    for (var, value) in other.0 {
+      match self.0.remove(&var) {
+        Some(value0) if value0 != value => conflicts.push((var, value0, value)),
+        _ => {
+          self.0.insert(var, value);
+        }
+      }
+    }
+
+    match conflicts.is_empty() {
+      true => Ok(self),
+      false => Err(conflicts),
+    }
+  }
 }
 
 impl<V: Hash + Eq, T> IntoIterator for DiscreteAssignment<V, T> {
@@ -93,3 +138,15 @@ impl<V: Hash + Eq, T> IntoIterator for DiscreteAssignment<V, T> {
     self.0.into_iter()
   }
 }
+
+impl<V: Hash + Eq> From<MineAssignment<V>> for DiscreteAssignment<V, bool> {
+  fn from(value: MineAssignment<V>) -> Self {
+    value.into_iter().collect()
+  }
+}
+
+impl<V: Hash + Eq> From<DiscreteAssignment<V, bool>> for MineAssignment<V> {
+  fn from(value: DiscreteAssignment<V, bool>) -> Self {
+    value.into_iter().collect()
+  }
+}
@@ -0,0 +1,469 @@
+//! Compact table constraints for variables with discrete values
+
+use std::collections::{HashMap, HashSet};
+use std::fmt::Debug;
+use std::hash::{Hash, Hasher};
+use std::rc::Rc;
+
+use crate::constraint::Domains;
+use crate::prelude::{Assignment, Constraint};
+use crate::systems::generic::{assignment::DiscreteAssignment, errors::DiscreteConflicts};
+
+/// A set of tuple indexes, stored as one bit per tuple
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct Bitset(Vec<u64>);
+
+impl Bitset {
+  /// A set containing no tuples, out of `len` tuples
+  fn empty(len: usize) -> Self {
+    Self(vec![0; len.div_ceil(64)])
+  }
+
+  /// A set containing all of `len` tuples
+  fn full(len: usize) -> Self {
+    let mut set = Self::empty(len);
+    (0..len).for_each(|idx| set.insert(idx));
+    set
+  }
+
+  fn insert(&mut self, idx: usize) {
+    self.0[idx / 64] |= 1 << (idx % 64);
+  }
+
+  fn is_empty(&self) -> bool {
+    self.0.iter().all(|&word| word == 0)
+  }
+
+  fn count(&self) -> usize {
+    self.0.iter().map(|word| word.count_ones() as usize).sum()
+  }
+
+  fn intersects(&self, other: &Self) -> bool {
+    self.0.iter().zip(&other.0).any(|(a, b)| a & b != 0)
+  }
+
+  fn is_subset(&self, other: &Self) -> bool {
+    self.0.iter().zip(&other.0).all(|(a, b)| a & !b == 0)
+  }
+
+  /// Removes tuples missing from `other`, returning whether any were removed
+  fn intersect_with(&mut self, other: &Self) -> bool {
+    let mut changed = false;
+    for (a, b) in self.0.iter_mut().zip(&other.0) {
+      changed |= *a & !b != 0;
+      *a &= b;
+    }
+    changed
+  }
+
+  fn union_with(&mut self, other: &Self) {
+    self.0.iter_mut().zip(&other.0).for_each(|(a, b)| *a |= b);
+  }
+
+  /// Adds the tuples that are in every one of `masks`
+  fn union_with_all(&mut self, masks: &[&Self]) {
+    for (idx, word) in self.0.iter_mut().enumerate() {
+      *word |= masks.iter().fold(!0, |acc, mask| acc & mask.0[idx]);
+    }
+  }
+
+  fn difference_with(&mut self, other: &Self) {
+    self.0.iter_mut().zip(&other.0).for_each(|(a, b)| *a &= !b);
+  }
+
+  /// The indexes of tuples in the set, in ascending order
+  fn iter(&self) -> impl Iterator<Item = usize> + '_ {
+    self.0.iter().enumerate().flat_map(|(idx, &word)| {
+      (0..64)
+        .filter(move |bit| word & (1 << bit) != 0)
+        .map(move |bit| idx * 64 + bit)
+    })
+  }
+}
+
+/// The assignments of a compact constraint, which are never modified once constructed
+#[derive(Debug)]
+struct Table<V, T> {
+  /// All variables the table was constructed with
+  variables: Vec<V>,
+  /// Each assignment, with values in the order of `variables`
+  tuples: Vec<Vec<T>>,
+  /// For each variable, the tuples that give it each value
+  supports: Vec<HashMap<T, Bitset>>,
+}
+
+/// A form of Constraints on discrete variables, using compact tables.
+///
+/// Assignments are stored once in a shared, immutable table,\
+/// so cloning and decomposing a constraint only copies a bitset of the assignments that remain.\
+/// As a [`System`] explores branches on copies of itself, this bitset is copied rather than trailed.
+///
+/// Reductions intersect bitmasks of the assignments supporting each value of each variable,\
+/// in the style of the Compact-Table algorithm, without hashing any assignments.\
+/// This makes them word-parallel over the assignments that remain.\
+/// Solving chains of tables with hundreds to thousands of assignments\
+/// is around 3 times faster than with [`DiscreteConstraint`]s, see `benches/compact.rs`.
+///
+/// This allows the same assignments as a [`DiscreteConstraint`] constructed in the same way.
+///
+/// ## Examples
+///
+/// ```
+/// # use farc3::prelude::*;
+/// let cons0 = CompactConstraint::from_iter([
+///   [("a", 1), ("b", 2)],
+///   [("a", 2), ("b", 1)],
+/// ]);
+/// let cons1 = CompactConstraint::from_iter([
+///   [("b", 2), ("c", 3)],
+///   [("b", 3), ("c", 1)],
+/// ]);
+///
+/// let mut sys = System::from([cons0, cons1]);
+/// let sltn = sys.pop_solution().unwrap();
+/// assert_eq!(sltn.get(&"a"), Some(&1));
+/// assert_eq!(sltn.get(&"c"), Some(&3));
+/// ```
+///
+/// [`DiscreteConstraint`]: crate::systems::generic::constraint::DiscreteConstraint
+/// [`System`]: crate::system::System
+#[derive(Debug)]
+pub struct CompactConstraint<V, T> {
+  /// The table of all assignments
+  table: Rc<Table<V, T>>,
+  /// The indexes of variables in the table that haven't been popped
+  vars: Vec<usize>,
+  /// The assignments in the table that remain
+  live: Bitset,
+}
+
+impl<V, T> Clone for CompactConstraint<V, T> {
+  fn clone(&self) -> Self {
+    Self {
+      table: self.table.clone(),
+      vars: self.vars.clone(),
+      live: self.live.clone(),
+    }
+  }
+}
+
+/// Constraints are only equal when they share a table,\
+/// as comparing the assignments of separate tables would be expensive.
+impl<V, T> PartialEq for CompactConstraint<V, T> {
+  fn eq(&self, other: &Self) -> bool {
+    Rc::ptr_eq(&self.table, &other.table) && self.vars == other.vars && self.live == other.live
+  }
+}
+impl<V, T> Eq for CompactConstraint<V, T> {}
+
+impl<V, T> Hash for CompactConstraint<V, T> {
+  fn hash<H: Hasher>(&self, state: &mut H) {
+    Rc::as_ptr(&self.table).hash(state);
+    self.vars.hash(state);
+    self.live.hash(state);
+  }
+}
+
+impl<V, T, I> FromIterator<I> for CompactConstraint<V, T>
+where
+  V: Hash + Eq + Debug,
+  T: Hash + Eq + Clone,
+  I: IntoIterator<Item = (V, T)>,
+{
+  fn from_iter<U: IntoIterator<Item = I>>(iter: U) -> Self {
+    let mut iters = iter.into_iter();
+    let Some(assigns) = iters.next() else {
+      return Self::new(vec![], vec![]);
+    };
+    let (variables, first): (Vec<V>, Vec<T>) = assigns.into_iter().unzip();
+    let positions: HashMap<&V, usize> = variables
+      .iter()
+      .enumerate()
+      .map(|(idx, var)| (var, idx))
+      .collect();
+
+    // order the values of each assignment by the variables of the first
+    let mut seen = HashSet::new();
+    let mut tuples = vec![];
+    let rest = iters.map(|assigns| {
+      let mut values: Vec<_> = assigns
+        .into_iter()
+        .map(|(var, value)| (positions.get(&var).copied(), value))
+        .collect();
+      assert!(
+        values.len() == variables.len() && values.iter().all(|(idx, _)| idx.is_some()),
+        "variables are not consistent when constructing compact constraint\n\
+        help: expected all assignments to use the variables {:?}",
+        variables
+      );
+      values.sort_by_key(|(idx, _)| *idx);
+      values.into_iter().map(|(_, value)| value).collect()
+    });
+    for values in [first].into_iter().chain(rest) {
+      if seen.insert(values.clone()) {
+        tuples.push(values);
+      }
+    }
+    Self::new(variables, tuples)
+  }
+}
+
+impl<V, T: Hash + Eq + Clone> CompactConstraint<V, T> {
+  /// Constructs a constraint from distinct tuples of values for `variables`
+  fn new(variables: Vec<V>, tuples: Vec<Vec<T>>) -> Self {
+    let len = tuples.len();
+    let mut supports = vec![HashMap::<T, Bitset>::new(); variables.len()];
+    for (tuple, values) in tuples.iter().enumerate() {
+      for (masks, value) in supports.iter_mut().zip(values) {
+        masks
+          .entry(value.clone())
+          .or_insert_with(|| Bitset::empty(len))
+          .insert(tuple);
+      }
+    }
+
+    Self {
+      vars: (0..variables.len()).collect(),
+      live: Bitset::full(len),
+      table: Rc::new(Table {
+        variables,
+        tuples,
+        supports,
+      }),
+    }
+  }
+}
+
+impl<V: Hash + Eq + Clone, T: Hash + Eq + Clone> CompactConstraint<V, T> {
+  /// The values of a tuple for the given variable indexes
+  fn project(&self, tuple: usize, idxs: &[usize]) -> Vec<T> {
+    let values = &self.table.tuples[tuple];
+    idxs.iter().map(|&idx| values[idx].clone()).collect()
+  }
+
+  /// The distinct values that remaining tuples give the given variable indexes
+  fn projections(&self, idxs: &[usize]) -> HashSet<Vec<T>> {
+    self
+      .live
+      .iter()
+      .map(|tuple| self.project(tuple, idxs))
+      .collect()
+  }
+
+  /// The pairs of indexes in `self` and `other` for their shared variables
+  fn shared(&self, other: &Self) -> Vec<(usize, usize)> {
+    let positions: HashMap<&V, usize> = other
+      .vars
+      .iter()
+      .map(|&idx| (&other.table.variables[idx], idx))
+      .collect();
+
+    self
+      .vars
+      .iter()
+      .filter_map(|&idx0| Some((idx0, *positions.get(&self.table.variables[idx0])?)))
+      .collect()
+  }
+
+  /// The tuples that give the variable at `idx` one of the given values
+  fn supported_by<'a>(&self, idx: usize, values: impl IntoIterator<Item = &'a T>) -> Bitset
+  where
+    T: 'a,
+  {
+    let mut mask = Bitset::empty(self.table.tuples.len());
+    for value in values {
+      if let Some(support) = self.table.supports[idx].get(value) {
+        mask.union_with(support);
+      }
+    }
+    mask
+  }
+
+  /// The values of the variable at `idx` that remaining tuples give it
+  fn values(&self, idx: usize) -> impl Iterator<Item = &T> {
+    self.table.supports[idx]
+      .iter()
+      .filter(|(_, mask)| mask.intersects(&self.live))
+      .map(|(value, _)| value)
+  }
+}
+
+impl<V, T> Constraint for CompactConstraint<V, T>
+where
+  V: Hash + Eq + Clone + Debug,
+  T: Hash + Eq + Clone + Debug,
+{
+  type Var = V;
+  type Solution = DiscreteAssignment<V, T>;
+  type ConflictErr = DiscreteConflicts<V, T>;
+
+  fn size(&self) -> usize {
+    self.live.count()
+  }
+
+  fn variables(&self) -> impl Iterator<Item = Self::Var> {
+    self
+      .vars
+      .iter()
+      .map(|&idx| self.table.variables[idx].clone())
+  }
+
+  fn decompositions(&self) -> impl Iterator<Item = Self> {
+    self.live.iter().map(|tuple| {
+      let mut live = Bitset::empty(self.table.tuples.len());
+      live.insert(tuple);
+      Self {
+        table: self.table.clone(),
+        vars: self.vars.clone(),
+        live,
+      }
+    })
+  }
+
+  fn reduce(&mut self, other: &Self) -> Result<bool, Self::ConflictErr> {
+    let shared = self.shared(other);
+    if shared.is_empty() {
+      return Ok(false);
+    }
+
+    // a single shared variable is supported by the values `other` gives it,
+    // several are supported by the tuples matching each remaining tuple of `other`
+    let supported = match shared[..] {
+      [(idx0, idx1)] => self.supported_by(idx0, other.values(idx1)),
+      _ => {
+        let mut supported = Bitset::empty(self.table.tuples.len());
+        for tuple in other.live.iter() {
+          let values = &other.table.tuples[tuple];
+          let masks: Option<Vec<_>> = shared
+            .iter()
+            .map(|&(idx0, idx1)| self.table.supports[idx0].get(&values[idx1]))
+            .collect();
+          if let Some(masks) = masks {
+            supported.union_with_all(&masks);
+          }
+        }
+        supported
+      }
+    };
+
+    if !self.live.intersects(&supported) {
+      let (idxs0, idxs1): (Vec<_>, Vec<_>) = shared.into_iter().unzip();
+      return Err(DiscreteConflicts {
+        shared: idxs0
+          .iter()
+          .map(|&idx| self.table.variables[idx].clone())
+          .collect(),
+        unsupported: self.projections(&idxs0).into_iter().collect(),
+        other_unsupported: other.projections(&idxs1).into_iter().collect(),
+      });
+    }
+    Ok(self.live.intersect_with(&supported))
+  }
+
+  fn pop_solution(&mut self) -> Option<Self::Solution> {
+    let tuple = self.live.iter().next()?;
+    if self.vars.is_empty() {
+      return None;
+    }
+
+    // a variable is decided when every remaining tuple supports its value
+    let values = &self.table.tuples[tuple];
+    let (decided, rest): (Vec<usize>, Vec<usize>) = self
+      .vars
+      .iter()
+      .partition(|&&idx| self.live.is_subset(&self.table.supports[idx][&values[idx]]));
+    self.vars = rest;
+
+    Some(
+      decided
+        .into_iter()
+        .map(|idx| (self.table.variables[idx].clone(), values[idx].clone()))
+        .collect(),
+    )
+  }
+
+  fn is_satisfied(&self, solution: &Self::Solution) -> bool {
+    let mut live = self.live.clone();
+    for &idx in &self.vars {
+      let Some(value) = solution.get(&self.table.variables[idx]) else {
+        return false;
+      };
+      live.intersect_with(&self.supported_by(idx, [value]));
+    }
+    !live.is_empty()
+  }
+
+  /// Excluding a decomposition removes its assignment from `self`
+  fn exclude(&self, decomposition: &Self) -> Option<Self> {
+    if !Rc::ptr_eq(&self.table, &decomposition.table) || self.vars != decomposition.vars {
+      return None;
+    }
+    let mut cons = self.clone();
+    cons.live.difference_with(&decomposition.live);
+    Some(cons)
+  }
+
+  fn split_on(&self, var: &V) -> Option<Vec<Self>> {
+    let idx = *self
+      .vars
+      .iter()
+      .find(|&&idx| &self.table.variables[idx] == var)?;
+
+    let splits = self.table.supports[idx].values().filter_map(|mask| {
+      let mut live = self.live.clone();
+      live.intersect_with(mask);
+      (!live.is_empty()).then(|| Self {
+        table: self.table.clone(),
+        vars: self.vars.clone(),
+        live,
+      })
+    });
+    Some(splits.collect())
+  }
+
+  fn subsumes(&self, other: &Self) -> bool {
+    let shared = self.shared(other);
+    if shared.len() != other.vars.len() {
+      return false;
+    }
+
+    let (idxs0, idxs1): (Vec<_>, Vec<_>) = shared.into_iter().unzip();
+    let supports = other.projections(&idxs1);
+    self
+      .live
+      .iter()
+      .all(|tuple| supports.contains(&self.project(tuple, &idxs0)))
+  }
+
+  fn supports(&self) -> Domains<V, T> {
+    self
+      .vars
+      .iter()
+      .map(|&idx| {
+        let var = self.table.variables[idx].clone();
+        (var, self.values(idx).cloned().collect())
+      })
+      .collect()
+  }
+
+  fn restrict(&mut self, domains: &Domains<V, T>) -> Result<bool, Self::ConflictErr> {
+    let mut restricted = false;
+    for &idx in &self.vars {
+      let var = &self.table.variables[idx];
+      let Some(values) = domains.get(var) else {
+        continue;
+      };
+
+      let supported = self.supported_by(idx, values);
+      if !self.live.intersects(&supported) {
+        return Err(DiscreteConflicts {
+          shared: vec![var.clone()],
+          unsupported: self.values(idx).map(|value| vec![value.clone()]).collect(),
+          other_unsupported: values.iter().map(|value| vec![value.clone()]).collect(),
+        });
+      }
+      restricted |= self.live.intersect_with(&supported);
+    }
+    Ok(restricted)
+  }
+}
@@ -5,9 +5,14 @@ use std::fmt::Debug;
 use std::hash::Hash;
 use std::mem;
 
+use crate::systems::generic::errors::DiscreteConflicts;
 use crate::systems::generic::utils::IteratorPartition;
 use crate::utils::NewHashSet;
-use crate::{prelude::Constraint, systems::generic::assignment::DiscreteAssignment};
+use crate::{
+  constraint::Domains,
+  prelude::{Assignment, Constraint},
+  systems::generic::assignment::DiscreteAssignment,
+};
 
 /// A generic form of Constraints on discrete variables.
 ///
@@ -22,7 +27,7 @@ use crate::{prelude::Constraint, systems::generic::assignment::DiscreteAssignmen
 ///
 /// Than specialised implementations of constraints.\
 /// If you want a more performant implementation, check out others in [`crate::systems`].
-#[derive(Debug, Hash, PartialEq, Eq)]
+#[derive(Debug, Clone, Hash, PartialEq, Eq)]
 pub struct DiscreteConstraint<V, T: Hash + Eq> {
   variables: Vec<V>,
   assignments: NewHashSet<Vec<T>>,
@@ -66,11 +71,14 @@ impl<V: Hash + Eq + Debug, T: Hash + Eq, I: IntoIterator<Item = (V, T)>> FromIte
   }
 }
 
-impl<V: Hash + Eq + Clone, T: Hash + Eq + Clone> Constraint for DiscreteConstraint<V, T> {
+impl<V, T> Constraint for DiscreteConstraint<V, T>
+where
+  V: Hash + Eq + Clone + Debug,
+  T: Hash + Eq + Clone + Debug,
+{
   type Var = V;
   type Solution = DiscreteAssignment<V, T>;
-  // @todo give a more informative error type
-  type ConflictErr = ();
+  type ConflictErr = DiscreteConflicts<V, T>;
 
   fn size(&self) -> usize {
     self.assignments.len()
@@ -107,26 +115,35 @@ impl<V: Hash + Eq + Clone, T: Hash + Eq + Clone> Constraint for DiscreteConstrai
       })
       .collect();
 
-    let len = self.assignments.len();
-
-    self.assignments.retain(|values0| {
-      // if there's some "supporting" assignment in `other`
-      // that doesn't contradict with this assignment, retain it.
-      other.assignments.iter().any(|values1| {
-        // whether `values1` "supports" `values0`
-        idxs
-          .iter()
-          .map(|&(idx0, idx1)| (&values0[idx0], &values1[idx1]))
-          .all(|(value0, value1)| value0 == value1)
-      })
-    });
+    let (idxs0, idxs1): (Vec<_>, Vec<_>) = idxs.into_iter().unzip();
+    let supports: HashSet<Vec<T>> = other
+      .assignments
+      .iter()
+      .map(|values1| project(values1, &idxs1))
+      .collect();
 
-    let new_len = self.assignments.len();
-    if 0 < new_len {
-      Ok(new_len < len)
-    } else {
-      Err(())
+    // if there's some "supporting" assignment in `other`
+    // that doesn't contradict with an assignment, retain it.
+    let (retained, removed): (Vec<_>, Vec<_>) = mem::take(&mut self.assignments)
+      .into_iter()
+      .partition(|values0| supports.contains(&project(values0, &idxs0)));
+
+    if retained.is_empty() {
+      self.assignments = removed.into_iter().collect();
+      let unsupported: HashSet<_> = self
+        .assignments
+        .iter()
+        .map(|values0| project(values0, &idxs0))
+        .collect();
+      return Err(DiscreteConflicts {
+        shared: project(&self.variables, &idxs0),
+        unsupported: unsupported.into_iter().collect(),
+        other_unsupported: supports.into_iter().collect(),
+      });
     }
+
+    self.assignments = retained.into_iter().collect();
+    Ok(!removed.is_empty())
   }
 
   fn pop_solution(&mut self) -> Option<Self::Solution> {
@@ -140,9 +157,247 @@ impl<V: Hash + Eq + Clone, T: Hash + Eq + Clone> Constraint for DiscreteConstrai
     let values = cons.assignments.into_iter().next()?;
     Some(cons.variables.into_iter().zip(values).collect())
   }
+
+  fn is_satisfied(&self, solution: &Self::Solution) -> bool {
+    let values: Option<Vec<T>> = self
+      .variables
+      .iter()
+      .map(|var| solution.get(var).cloned())
+      .collect();
+    values.is_some_and(|values| self.assignments.contains(&values))
+  }
+
+  /// Excluding a decomposition removes its assignment from `self`
+  fn exclude(&self, decomposition: &Self) -> Option<Self> {
+    if self.variables != decomposition.variables {
+      return None;
+    }
+    let mut cons = self.clone();
+    for values in decomposition.assignments.iter() {
+      cons.assignments.remove(values);
+    }
+    Some(cons)
+  }
+
+  fn split_on(&self, var: &V) -> Option<Vec<Self>> {
+    let idx = self.variables.iter().position(|other| other == var)?;
+    let values: HashSet<&T> = self.assignments.iter().map(|values| &values[idx]).collect();
+    Some(
+      values
+        .into_iter()
+        .map(|value| self.select(var, value))
+        .collect(),
+    )
+  }
+
+  /// `self` subsumes `other` when projecting `self` onto the variables of `other`\
+  /// only gives assignments that `other` allows
+  fn subsumes(&self, other: &Self) -> bool {
+    let idxs: Option<Vec<_>> = other
+      .variables
+      .iter()
+      .map(|var| self.variables.iter().position(|other| other == var))
+      .collect();
+    let Some(idxs) = idxs else {
+      return false;
+    };
+
+    self
+      .assignments
+      .iter()
+      .all(|values| other.assignments.contains(&project(values, &idxs)))
+  }
+
+  fn supports(&self) -> Domains<V, T> {
+    self
+      .variables
+      .iter()
+      .enumerate()
+      .map(|(idx, var)| {
+        let values = self.assignments.iter().map(|values| values[idx].clone());
+        (var.clone(), values.collect())
+      })
+      .collect()
+  }
+
+  fn restrict(&mut self, domains: &Domains<V, T>) -> Result<bool, Self::ConflictErr> {
+    let mut restricted = false;
+    for var in self.variables.clone() {
+      let Some(values) = domains.get(&var) else {
+        continue;
+      };
+      // reducing by a single variable constraint keeps conflicts consistent with `reduce`
+      let domain = Self {
+        variables: vec![var],
+        assignments: values.iter().map(|value| vec![value.clone()]).collect(),
+      };
+      restricted |= self.reduce(&domain)?;
+    }
+    Ok(restricted)
+  }
+}
+
+/// The values at the given indexes of an assignment
+fn project<T: Clone>(values: &[T], idxs: &[usize]) -> Vec<T> {
+  idxs.iter().map(|&idx| values[idx].clone()).collect()
+}
+
+impl<V: Eq + Clone, T: Hash + Eq + Clone> DiscreteConstraint<V, T> {
+  /// The indexes of the given variables in `self`, skipping those `self` doesn't affect
+  fn idxs_of<'a>(&self, vars: impl IntoIterator<Item = &'a V>) -> Vec<usize>
+  where
+    V: 'a,
+  {
+    let mut idxs = vec![];
+    for var in vars {
+      let Some(idx) = self.variables.iter().position(|other| other == var) else {
+        continue;
+      };
+      if !idxs.contains(&idx) {
+        idxs.push(idx);
+      }
+    }
+    idxs
+  }
+
+  /// The natural join of two constraints,\
+  /// allowing the assignments that both constraints agree on for their shared variables.
+  ///
+  /// ## Returns
+  ///
+  /// A constraint on the variables of `self`, followed by the other variables of `other`
+  ///
+  /// ## Examples
+  ///
+  /// ```
+  /// # use farc3::prelude::*;
+  /// let cons0 = DiscreteConstraint::from_iter([[("a", 1), ("b", 2)], [("a", 2), ("b", 1)]]);
+  /// let cons1 = DiscreteConstraint::from_iter([[("b", 2), ("c", 3)], [("b", 2), ("c", 4)]]);
+  ///
+  /// assert_eq!(
+  ///   cons0.join(&cons1),
+  ///   DiscreteConstraint::from_iter([
+  ///     [("a", 1), ("b", 2), ("c", 3)],
+  ///     [("a", 1), ("b", 2), ("c", 4)],
+  ///   ])
+  /// );
+  /// ```
+  pub fn join(&self, other: &Self) -> Self {
+    let shared0 = self.idxs_of(&other.variables);
+    let shared1 = other.idxs_of(&self.variables);
+    let rest1: Vec<_> = (0..other.variables.len())
+      .filter(|idx| !shared1.contains(idx))
+      .collect();
+
+    // group the assignments of `other` by their values for shared variables
+    let mut groups: HashMap<Vec<T>, Vec<Vec<T>>> = HashMap::new();
+    for values1 in other.assignments.iter() {
+      groups
+        .entry(project(values1, &shared1))
+        .or_default()
+        .push(project(values1, &rest1));
+    }
+
+    let mut variables = self.variables.clone();
+    variables.extend(project(&other.variables, &rest1));
+
+    let mut assignments = NewHashSet::default();
+    for values0 in self.assignments.iter() {
+      let Some(group) = groups.get(&project(values0, &shared0)) else {
+        continue;
+      };
+      for rest in group {
+        let mut values = values0.clone();
+        values.extend(rest.iter().cloned());
+        assignments.insert(values);
+      }
+    }
+
+    Self {
+      variables,
+      assignments,
+    }
+  }
+
+  /// Projects `self` onto a subset of its variables,\
+  /// allowing the values for those variables that some assignment of `self` gives.
+  ///
+  /// ## Arguments
+  ///
+  /// - `vars`: the variables to keep, where variables `self` doesn't affect are ignored
+  ///
+  /// ## Returns
+  ///
+  /// A constraint on `vars`, in the order they were given
+  pub fn project(&self, vars: impl IntoIterator<Item = V>) -> Self {
+    let vars: Vec<V> = vars.into_iter().collect();
+    let idxs = self.idxs_of(&vars);
+
+    Self {
+      variables: project(&self.variables, &idxs),
+      assignments: self
+        .assignments
+        .iter()
+        .map(|values| project(values, &idxs))
+        .collect(),
+    }
+  }
+
+  /// Selects the assignments of `self` that give `var` the value `value`
+  ///
+  /// ## Returns
+  ///
+  /// A constraint on the same variables as `self`,\
+  /// which is unchanged if `self` doesn't affect `var`.
+  pub fn select(&self, var: &V, value: &T) -> Self {
+    let Some(idx) = self.variables.iter().position(|other| other == var) else {
+      return self.clone();
+    };
+
+    Self {
+      variables: self.variables.clone(),
+      assignments: self
+        .assignments
+        .iter()
+        .filter(|values| &values[idx] == value)
+        .cloned()
+        .collect(),
+    }
+  }
 }
 
 impl<V, T: Hash + Eq> DiscreteConstraint<V, T> {
+  /// Constructs a constraint from a table of the values its variables can take
+  ///
+  /// ## Arguments
+  ///
+  /// - `variables`: the variables of the table, in the order of its columns
+  /// - `rows`: the values of `variables` in each allowed assignment
+  ///
+  /// ## Returns
+  ///
+  /// A new [`DiscreteConstraint`], which has no solutions if `rows` is empty
+  pub fn from_table(variables: Vec<V>, rows: impl IntoIterator<Item = Vec<T>>) -> Self {
+    let assignments: NewHashSet<_> = rows.into_iter().collect();
+    assert!(
+      assignments
+        .iter()
+        .all(|values| values.len() == variables.len()),
+      "rows don't have a value for every variable when constructing generic constraint"
+    );
+    Self {
+      variables,
+      assignments,
+    }
+  }
+
+  /// The variables of `self` in the order of its columns,\
+  /// and the values they take in each allowed assignment
+  pub fn table(&self) -> (&[V], impl Iterator<Item = &[T]>) {
+    let rows = self.assignments.iter().map(|values| values.as_slice());
+    (&self.variables, rows)
+  }
+
   /// Finds the indexes that, for all value assignments, have the same value.
   fn common_idxs(&self) -> Option<Vec<usize>> {
     // calculate the indexes of values that are common to all assignments
@@ -0,0 +1,56 @@
+//! Errors produced when discrete constraints conflict
+
+use std::error::Error;
+use std::fmt::{self, Debug, Display};
+
+/// The error that is produced when 2 discrete constraints conflict.
+///
+/// Constraints conflict when none of their assignments agree on the variables they share.\
+/// Assignments are given as values for the shared variables, in the order of [`Self::shared`].
+///
+/// ## Example
+///
+/// ```
+/// # use farc3::constraint::Constraint;
+/// # use farc3::systems::generic::{
+/// #   constraint::DiscreteConstraint,
+/// #   errors::DiscreteConflicts
+/// # };
+/// let mut cons0 = DiscreteConstraint::from_iter([
+///   [("a", true), ("b", true)],
+///   [("a", true), ("b", false)],
+/// ]);
+/// let cons1 = DiscreteConstraint::from_iter([[("a", false), ("c", true)]]);
+///
+/// let res = cons0.reduce(&cons1);
+/// assert_eq!(
+///   res,
+///   Err(DiscreteConflicts {
+///     shared: vec!["a"],
+///     unsupported: vec![vec![true]],
+///     other_unsupported: vec![vec![false]],
+///   })
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiscreteConflicts<V, T> {
+  /// The variables shared by both constraints
+  pub shared: Vec<V>,
+  /// The assignments of the constraint being reduced that had no support
+  pub unsupported: Vec<Vec<T>>,
+  /// The assignments of the constraint it was reduced by that had no support
+  pub other_unsupported: Vec<Vec<T>>,
+}
+
+impl<V: Debug, T: Debug> Display for DiscreteConflicts<V, T> {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(
+      f,
+      "no assignment to {:?} is allowed by both constraints, \
+      one allows {:?} and the other allows {:?}",
+      self.shared, self.unsupported, self.other_unsupported
+    )
+  }
+}
+
+impl<V: Debug, T: Debug> Error for DiscreteConflicts<V, T> {}
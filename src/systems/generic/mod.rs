@@ -1,11 +1,14 @@
 //! Generic Constraints for CSPs with discrete variables.\
-//! These do not have efficient implementations.
+//! These store tables of assignments, either as sets of values or as compact bitsets.
 pub mod assignment;
+pub mod compact;
 pub mod constraint;
+pub mod errors;
 pub mod utils;
 
 pub mod prelude {
   //! Common exports for generic constraint systems
+  pub use super::compact::CompactConstraint;
   pub use super::constraint::DiscreteConstraint;
 }
 
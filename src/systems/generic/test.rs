@@ -45,6 +45,38 @@ mod assignments {
     assert_eq!(var_map.get("c"), None); // contradiction
     assert_eq!(var_map.get("d"), Some(&1));
   }
+
+  #[test]
+  fn checked_union() {
+    let assign0 = DiscreteAssignment::from_iter([("a", 1), ("b", 2)]);
+    let assign1 = DiscreteAssignment::from_iter([/*     */ ("b", 2), ("d", 1)]);
+    let assign = assign0.checked_union(assign1).unwrap();
+    assert_eq!(
+      assign,
+      DiscreteAssignment::from_iter([("a", 1), ("b", 2), ("d", 1)])
+    );
+
+    let assign0 = DiscreteAssignment::from_iter([("a", 1), ("b", 2), ("c", 1)]);
+    let assign1 = DiscreteAssignment::from_iter([/*     */ ("b", 2), ("c", 2), ("d", 1)]);
+    let conflicts = assign0.checked_union(assign1).unwrap_err();
+    assert_eq!(conflicts, vec![("c", 1, 2)]);
+  }
+  #[test]
+  fn lookup() {
+    let mut assign = DiscreteAssignment::from_iter([("a", 1), ("b", 2)]);
+    assert_eq!(assign.get(&"a"), Some(&1));
+    assert_eq!(assign.get(&"c"), None);
+    assert!(assign.contains(&"b"));
+    assert_eq!(assign.len(), 2);
+    assert!(!DiscreteAssignment::<&str, usize>::default().contains(&"a"));
+
+    assert_eq!(assign.insert("a", 3), Some(1));
+    assert_eq!(assign.insert("c", 1), None);
+    assert_eq!(assign.iter().count(), 3);
+
+    let assign = assign.restrict_to(["a", "c", "d"]);
+    assert_eq!(assign, DiscreteAssignment::from_iter([("a", 3), ("c", 1)]));
+  }
 }
 
 /// Unit testing generic [`Constraints`]
@@ -52,6 +84,7 @@ mod assignments {
 /// [`Constraints`]: crate::systems::generic::assignment::GenericConstraint
 mod constraints {
   use crate::prelude::{Constraint, DiscreteConstraint};
+  use crate::systems::generic::errors::DiscreteConflicts;
   use std::collections::{HashMap, HashSet};
 
   #[test]
@@ -183,8 +216,155 @@ mod constraints {
       [("a", false), ("b", false)],
     ]);
 
-    let res = cons0.reduce(&cons1);
-    assert_eq!(res, Err(()));
+    let mut err = cons0.reduce(&cons1).unwrap_err();
+    err.unsupported.sort();
+    err.other_unsupported.sort();
+    assert_eq!(
+      err,
+      DiscreteConflicts {
+        shared: vec!["a", "b"],
+        unsupported: vec![vec![true, false], vec![true, true]],
+        other_unsupported: vec![vec![false, false], vec![false, true]],
+      }
+    );
+
+    // the constraint is left unchanged by a failed reduction
+    assert_eq!(cons0.size(), 2);
+  }
+
+  #[test]
+  fn conflict_display() {
+    let mut cons0 = DiscreteConstraint::from_iter([[("a", 0), ("b", 1)]]);
+    let cons1 = DiscreteConstraint::from_iter([[("b", 2), ("c", 3)]]);
+
+    let err = cons0.reduce(&cons1).unwrap_err();
+    assert_eq!(
+      err.to_string(),
+      "no assignment to [\"b\"] is allowed by both constraints, \
+      one allows [[1]] and the other allows [[2]]"
+    );
+  }
+
+  #[test]
+  fn satisfied() {
+    let cons = DiscreteConstraint::from_iter([
+      [("a", 1), ("b", 2)], //
+      [("a", 2), ("b", 1)],
+    ]);
+    assert!(cons.is_satisfied(&[("a", 1), ("b", 2), ("c", 3)].into_iter().collect()));
+    assert!(!cons.is_satisfied(&[("a", 1), ("b", 1)].into_iter().collect()));
+    assert!(!cons.is_satisfied(&[("a", 1)].into_iter().collect()));
+  }
+
+  #[test]
+  fn subsumes() {
+    let cons = DiscreteConstraint::from_iter([
+      [("a", 1), ("b", 2), ("c", 1)], //
+      [("a", 2), ("b", 1), ("c", 1)],
+    ]);
+
+    // the projection of `cons` onto "b" and "c" is a subset
+    let weaker = DiscreteConstraint::from_iter([
+      [("c", 1), ("b", 2)],
+      [("c", 1), ("b", 1)],
+      [("c", 2), ("b", 1)],
+    ]);
+    assert!(cons.subsumes(&weaker));
+    assert!(!weaker.subsumes(&cons));
+
+    let other = DiscreteConstraint::from_iter([[("b", 2), ("c", 1)], [("b", 1), ("c", 2)]]);
+    assert!(!cons.subsumes(&other));
+
+    let unrelated = DiscreteConstraint::from_iter([[("d", 1)]]);
+    assert!(!cons.subsumes(&unrelated));
+  }
+
+  #[test]
+  fn join() {
+    let cons0 = DiscreteConstraint::from_iter([
+      [("a", 1), ("b", 2)], //
+      [("a", 2), ("b", 1)],
+      [("a", 3), ("b", 3)],
+    ]);
+    let cons1 = DiscreteConstraint::from_iter([
+      [("b", 1), ("c", 1)], //
+      [("b", 2), ("c", 1)],
+      [("b", 2), ("c", 2)],
+    ]);
+
+    let joined = cons0.join(&cons1);
+    assert_eq!(
+      joined,
+      DiscreteConstraint::from_iter([
+        [("a", 1), ("b", 2), ("c", 1)],
+        [("a", 1), ("b", 2), ("c", 2)],
+        [("a", 2), ("b", 1), ("c", 1)],
+      ])
+    );
+
+    // joining without shared variables gives every combination
+    let cons2 = DiscreteConstraint::from_iter([[("d", 1)], [("d", 2)]]);
+    assert_eq!(cons0.join(&cons2).size(), 6);
+
+    // joining with itself changes nothing
+    assert_eq!(cons0.join(&cons0), cons0);
+  }
+
+  #[test]
+  fn project() {
+    let cons = DiscreteConstraint::from_iter([
+      [("a", 1), ("b", 2), ("c", 1)],
+      [("a", 2), ("b", 1), ("c", 1)],
+      [("a", 2), ("b", 2), ("c", 1)],
+    ]);
+
+    assert_eq!(
+      cons.project(["c", "a", "d"]),
+      DiscreteConstraint::from_iter([
+        [("c", 1), ("a", 1)], //
+        [("c", 1), ("a", 2)],
+      ])
+    );
+    assert_eq!(cons.project(["c"]).size(), 1);
+    assert_eq!(cons.project([]).size(), 1);
+  }
+
+  #[test]
+  fn select() {
+    let cons = DiscreteConstraint::from_iter([
+      [("a", 1), ("b", 2)], //
+      [("a", 2), ("b", 1)],
+      [("a", 2), ("b", 2)],
+    ]);
+
+    assert_eq!(
+      cons.select(&"a", &2),
+      DiscreteConstraint::from_iter([
+        [("a", 2), ("b", 1)], //
+        [("a", 2), ("b", 2)],
+      ])
+    );
+    assert_eq!(cons.select(&"b", &3).size(), 0);
+    assert_eq!(cons.select(&"c", &1), cons);
+  }
+
+  #[test]
+  fn split_on() {
+    let cons = DiscreteConstraint::from_iter([
+      [("a", 1), ("b", 2)], //
+      [("a", 2), ("b", 1)],
+      [("a", 2), ("b", 2)],
+    ]);
+
+    let splits: HashSet<_> = cons.split_on(&"a").unwrap().into_iter().collect();
+    assert_eq!(
+      splits,
+      HashSet::from([
+        DiscreteConstraint::from_iter([[("a", 1), ("b", 2)]]),
+        DiscreteConstraint::from_iter([[("a", 2), ("b", 1)], [("a", 2), ("b", 2)]]),
+      ])
+    );
+    assert_eq!(cons.split_on(&"c"), None);
   }
 }
 
@@ -192,10 +372,12 @@ mod constraints {
 ///
 /// [`System`]: crate::system::System
 mod solver {
-  use std::collections::HashMap;
+  use std::collections::{HashMap, HashSet};
 
   use super::super::constraint::DiscreteConstraint;
-  use crate::prelude::System;
+  use crate::constraint::Branching;
+  use crate::prelude::{Assignment, Constraint, System};
+  use crate::system::Propagation;
 
   #[test]
   fn unresolvable() {
@@ -231,6 +413,33 @@ mod solver {
     assert_eq!(sys.len(), 0);
   }
 
+  /// Reducing with a constraint in the middle of the system updates constraints on either side,\
+  /// without moving the constraints that own heap allocations out of the system.
+  #[test]
+  fn minimise_around() {
+    let cons0 = DiscreteConstraint::from_iter([
+      [("a", true), ("b", true)], //
+      [("a", false), ("b", false)],
+    ]);
+    let cons1 = DiscreteConstraint::from_iter([
+      [("b", true), ("c", true)], //
+    ]);
+    let cons2 = DiscreteConstraint::from_iter([
+      [("c", true), ("d", false)], //
+      [("c", false), ("d", true)],
+    ]);
+
+    let mut sys = System::from_iter([cons0, cons1, cons2]);
+    sys.minimise().unwrap();
+
+    let sltn = sys.pop_solution().unwrap();
+    assert_eq!(
+      HashMap::from_iter(sltn),
+      HashMap::from([("a", true), ("b", true), ("c", true), ("d", false)])
+    );
+    assert_eq!(sys.len(), 0);
+  }
+
   /// The solver can fully solve a system of constraints
   #[test]
   fn full_minimise() {
@@ -288,4 +497,500 @@ mod solver {
       ])]
     );
   }
+
+  #[test]
+  fn verify() {
+    let cons0 = DiscreteConstraint::from_iter([
+      [("a", 1), ("b", 2)], //
+      [("a", 2), ("b", 1)],
+    ]);
+    let cons1 = DiscreteConstraint::from_iter([
+      [("b", 1), ("c", 1)], //
+      [("b", 2), ("c", 2)],
+    ]);
+    let sys = System::from_iter([cons0.clone(), cons1]);
+
+    let sltn = [("a", 2), ("b", 1), ("c", 1)].into_iter().collect();
+    assert!(sys.verify(&sltn).is_ok());
+
+    let sltn = [("a", 1), ("b", 1), ("c", 1)].into_iter().collect();
+    let violations = sys.verify(&sltn).unwrap_err();
+    assert_eq!(violations.violated, vec![&cons0]);
+    assert!(violations.unassigned.is_empty());
+
+    // no value of "b" is allowed alongside "a" = 3
+    let sltn = [("a", 3), ("c", 1)].into_iter().collect();
+    let violations = sys.verify(&sltn).unwrap_err();
+    assert_eq!(violations.violated, vec![&cons0]);
+    assert_eq!(violations.unassigned, HashSet::from(["b"]));
+  }
+
+  /// Domains are pruned along a chain of constraints
+  #[test]
+  fn arc_consistency() {
+    let less = |a, b| {
+      DiscreteConstraint::from_iter((0..4).flat_map(|i| (i + 1..4).map(move |j| [(a, i), (b, j)])))
+    };
+    let mut sys = System::from([less("a", "b"), less("b", "c"), less("c", "d")]);
+
+    let domains = sys.arc_consistency().unwrap().domains();
+    assert_eq!(
+      *domains,
+      HashMap::from([
+        ("a", HashSet::from([0])),
+        ("b", HashSet::from([1])),
+        ("c", HashSet::from([2])),
+        ("d", HashSet::from([3])),
+      ])
+    );
+
+    let sltn = sys.pop_solution().unwrap();
+    assert_eq!(
+      HashMap::from_iter(sltn),
+      HashMap::from([("a", 0), ("b", 1), ("c", 2), ("d", 3)])
+    );
+    assert!(sys.is_empty());
+  }
+
+  /// Domains outlive the constraints that pruned them,
+  /// so they restrict constraints inserted later
+  #[test]
+  fn stored_domains() {
+    let cons0 = DiscreteConstraint::from_iter([[("a", 1), ("b", 1)], [("a", 1), ("b", 2)]]);
+    let mut sys = System::from([cons0]);
+    let sltn = sys.pop_solution().unwrap();
+    assert_eq!(sltn.get(&"a"), Some(&1));
+    assert_eq!(sys.domains()[&"a"], HashSet::from([1]));
+
+    // no remaining constraint contains "a", so only its domain can prune this
+    let cons1 = DiscreteConstraint::from_iter([[("a", 1), ("c", 1)], [("a", 2), ("c", 2)]]);
+    sys.insert(cons1);
+    let sltn = sys.pop_solution().unwrap();
+    assert_eq!(sltn.get(&"c"), Some(&1));
+  }
+
+  #[test]
+  fn arc_consistency_conflicts() {
+    let cons0 = DiscreteConstraint::from_iter([[("a", 1), ("b", 1)], [("a", 1), ("b", 2)]]);
+    let cons1 = DiscreteConstraint::from_iter([[("a", 2), ("c", 1)], [("a", 3), ("c", 2)]]);
+
+    let mut sys = System::from([cons0, cons1]);
+    let err = sys.arc_consistency().unwrap_err();
+    assert_eq!(err.shared, vec!["a"]);
+    // the shared domain of "a" is emptied, so none of cons0's values are supported by it
+    assert_eq!(err.unsupported, vec![vec![1]]);
+    assert!(err.other_unsupported.is_empty());
+  }
+
+  /// Probing discards assignments that conflict, even when reducing can't
+  #[test]
+  fn probing() {
+    // "b" and "c" must differ, which rules out the last assignment
+    let cons0 = DiscreteConstraint::from_iter([
+      [("a", 0), ("b", 0), ("c", 1)],
+      [("a", 1), ("b", 1), ("c", 0)],
+      [("a", 1), ("b", 0), ("c", 0)],
+    ]);
+    let cons1 = DiscreteConstraint::from_iter([
+      [("b", 0), ("d", 0)], //
+      [("b", 1), ("d", 1)],
+    ]);
+    let cons2 = DiscreteConstraint::from_iter([
+      [("c", 0), ("d", 1)], //
+      [("c", 1), ("d", 0)],
+    ]);
+
+    let mut sys = System::from([cons0, cons1, cons2]);
+    sys.minimise().unwrap();
+    assert!(sys.iter().any(|cons| cons.size() == 3));
+
+    sys.probe().unwrap();
+    assert!(sys.iter().all(|cons| cons.size() <= 2));
+    assert_eq!(
+      sys.with_propagation(Propagation::Probing).solve().count(),
+      2
+    );
+  }
+
+  #[test]
+  fn simplify() {
+    let cons0 = DiscreteConstraint::from_iter([
+      [("a", 1), ("b", 2)], //
+      [("a", 2), ("b", 1)],
+    ]);
+    let cons1 = DiscreteConstraint::from_iter([[("a", 1)], [("a", 2)], [("a", 3)]]);
+    let cons2 = DiscreteConstraint::from_iter([[("b", 1), ("c", 1)], [("b", 1), ("c", 2)]]);
+
+    // of the equal constraints, only the last is kept
+    let mut sys = System::from([cons0.clone(), cons1.clone(), cons2, cons0.clone()]);
+    assert_eq!(sys.simplify(), vec![cons0, cons1]);
+    assert_eq!(sys.len(), 2);
+  }
+
+  /// Branching on values finds the same solutions as branching on decompositions
+  #[test]
+  fn branching() {
+    // "a", "b" and "c" are all different values in 0..4, where "a" + "b" == "c"
+    let differ = |x, y| {
+      DiscreteConstraint::from_iter((0..4).flat_map(|i| {
+        (0..4)
+          .filter(move |&j| j != i)
+          .map(move |j| [(x, i), (y, j)])
+      }))
+    };
+    let sum = DiscreteConstraint::from_iter(
+      (0..4).flat_map(|i| (0..4 - i).map(move |j| [("a", i), ("b", j), ("c", i + j)])),
+    );
+    let sys = System::from([differ("a", "b"), differ("b", "c"), differ("a", "c"), sum]);
+
+    let solutions = |sys: System<DiscreteConstraint<_, usize>>| {
+      let mut sltns: Vec<_> = sys
+        .solve()
+        .map(|sltn| {
+          let mut sltn: Vec<_> = sltn.into_iter().collect();
+          sltn.sort();
+          sltn
+        })
+        .collect();
+      sltns.sort();
+      sltns
+    };
+
+    let sltns = solutions(sys.clone());
+    assert_eq!(sltns.len(), 2);
+    assert_eq!(sys.branching(), Branching::Decompositions);
+    for branching in [Branching::Values, Branching::Binary] {
+      assert_eq!(solutions(sys.clone().with_branching(branching)), sltns);
+    }
+  }
+
+  /// Each solution is only found once
+  #[test]
+  fn unique_solutions() {
+    let cons0 = DiscreteConstraint::from_iter([
+      [("a", 1), ("b", 1)], //
+      [("a", 1), ("b", 2)],
+      [("a", 2), ("b", 2)],
+    ]);
+    let cons1 = DiscreteConstraint::from_iter([
+      [("b", 1), ("c", 1)], //
+      [("b", 2), ("c", 1)],
+      [("b", 2), ("c", 2)],
+    ]);
+    let sys = System::from([cons0, cons1]);
+
+    let sltns: Vec<HashMap<_, _>> = sys.solve().map(HashMap::from_iter).collect();
+    assert_eq!(sltns.len(), 5);
+    for (idx, sltn) in sltns.iter().enumerate() {
+      assert!(!sltns[idx + 1..].contains(sltn));
+    }
+  }
+
+  /// Memoising explored subsystems finds the same solutions
+  #[test]
+  fn transpositions() {
+    let pair = |x, y| {
+      DiscreteConstraint::from_iter([
+        [(x, 1), (y, 2)], //
+        [(x, 2), (y, 1)],
+        [(x, 2), (y, 2)],
+      ])
+    };
+    let sys = System::from([pair("a", "b"), pair("c", "d"), pair("b", "e")]);
+
+    let sltns: Vec<HashMap<_, _>> = sys.clone().solve().map(HashMap::from_iter).collect();
+    let memoised: Vec<HashMap<_, _>> = sys
+      .solve()
+      .with_transpositions(256)
+      .map(HashMap::from_iter)
+      .collect();
+    assert_eq!(memoised.len(), sltns.len());
+    for sltn in &memoised {
+      assert!(sltns.contains(sltn));
+    }
+  }
+
+  /// Solutions are found in lexicographic order
+  #[test]
+  fn ordered() {
+    let sys = System::from([
+      DiscreteConstraint::from_iter([
+        [("a", 1), ("b", 3)],
+        [("a", 2), ("b", 1)],
+        [("a", 3), ("b", 1)],
+        [("a", 3), ("b", 2)],
+      ]),
+      DiscreteConstraint::from_iter([
+        [("b", 1), ("c", 2)], //
+        [("b", 2), ("c", 3)],
+        [("b", 3), ("c", 1)],
+        [("b", 3), ("c", 3)],
+      ]),
+    ]);
+
+    let ordered: Vec<Vec<_>> = sys
+      .clone()
+      .solve_ordered(["c", "a", "b"], [3, 1, 2])
+      .map(|sltn| ["c", "a", "b"].map(|var| *sltn.get(&var).unwrap()).to_vec())
+      .collect();
+    assert_eq!(
+      ordered,
+      vec![
+        vec![3, 3, 2], //
+        vec![3, 1, 3],
+        vec![1, 1, 3],
+        vec![2, 3, 1],
+        vec![2, 2, 1],
+      ]
+    );
+
+    let kth = sys.kth_solution(["c", "a", "b"], [3, 1, 2], 3).unwrap();
+    assert_eq!(kth.get(&"a"), Some(&3));
+  }
+}
+
+/// Testing explanations for why variables were assigned
+mod explanations {
+  use std::collections::HashMap;
+
+  use super::super::constraint::DiscreteConstraint;
+  use crate::prelude::System;
+
+  #[test]
+  fn reduced() {
+    let cons0 = DiscreteConstraint::from_iter([
+      [("a", true), ("b", false), ("c", true)],
+      [("a", false), ("b", true), ("c", true)],
+    ]);
+    let cons1 = DiscreteConstraint::from_iter([
+      [("a", true), ("b", true)], //
+      [("a", true), ("b", false)],
+    ]);
+
+    let mut sys = System::from_iter([cons0, cons1]).explained();
+    let sltn = sys.pop_solution().unwrap();
+    assert_eq!(
+      HashMap::from_iter(sltn.solution().clone()),
+      HashMap::from([("a", true), ("b", false), ("c", true)])
+    );
+
+    // "b" is only decided once the constraints have reduced each other
+    let reason = sltn.reason(&"b").unwrap();
+    assert_eq!(reason.premises().len(), 2);
+    assert!(!reason.reductions().is_empty());
+  }
+}
+
+/// Unit testing [`CompactConstraints`]
+///
+/// [`CompactConstraints`]: crate::systems::generic::compact::CompactConstraint
+mod compact {
+  use std::collections::{HashMap, HashSet};
+
+  use super::super::{compact::CompactConstraint, constraint::DiscreteConstraint};
+  use crate::constraint::Branching;
+  use crate::prelude::{Constraint, System};
+  use crate::system::Propagation;
+  use crate::systems::generic::errors::DiscreteConflicts;
+
+  /// Constraints that `a` and `b` take different colours
+  fn differ<C: FromIterator<[(&'static str, usize); 2]>>(a: &'static str, b: &'static str) -> C {
+    (0..3)
+      .flat_map(|i| {
+        (0..3)
+          .filter(move |&j| j != i)
+          .map(move |j| [(a, i), (b, j)])
+      })
+      .collect()
+  }
+
+  /// The edges of a map of Australia, which is coloured with 3 colours
+  const EDGES: [(&str, &str); 9] = [
+    ("wa", "nt"),
+    ("wa", "sa"),
+    ("nt", "sa"),
+    ("nt", "q"),
+    ("sa", "q"),
+    ("sa", "nsw"),
+    ("sa", "v"),
+    ("q", "nsw"),
+    ("nsw", "v"),
+  ];
+
+  /// The solutions of a system, in a form that can be compared
+  fn solutions<C>(sys: System<C>) -> HashSet<Vec<(&'static str, usize)>>
+  where
+    C: Constraint<Var = &'static str> + std::hash::Hash + Eq + Clone,
+    C::Solution: Default + Clone + IntoIterator<Item = (&'static str, usize)>,
+  {
+    sys
+      .solve()
+      .map(|sltn| {
+        let mut sltn: Vec<_> = sltn.into_iter().collect();
+        sltn.sort();
+        sltn
+      })
+      .collect()
+  }
+
+  #[test]
+  fn construction() {
+    // assignments can list variables in any order, and duplicates are ignored
+    let cons = CompactConstraint::from_iter([
+      vec![("a", 1), ("b", 2)],
+      vec![("b", 1), ("a", 2)],
+      vec![("a", 1), ("b", 2)],
+    ]);
+    assert_eq!(cons.size(), 2);
+    assert_eq!(cons.variables().collect::<Vec<_>>(), vec!["a", "b"]);
+    assert!(cons.is_satisfied(&[("a", 2), ("b", 1)].into()));
+    assert!(!cons.is_satisfied(&[("a", 2), ("b", 2)].into()));
+
+    let empty = CompactConstraint::<&str, usize>::from_iter(Vec::<[(&str, usize); 0]>::new());
+    assert_eq!(empty.size(), 0);
+  }
+
+  #[test]
+  fn reduce() {
+    let mut cons0 = CompactConstraint::from_iter([
+      [("a", 1), ("b", 2), ("c", 1)],
+      [("a", 2), ("b", 1), ("c", 1)],
+      [("a", 2), ("b", 2), ("c", 2)],
+    ]);
+    let cons1 = CompactConstraint::from_iter([
+      [("b", 2), ("c", 1)], //
+      [("b", 1), ("c", 2)],
+    ]);
+
+    // each value of "b" and "c" is supported, but only one pair of them is
+    assert!(cons0.reduce(&cons1).unwrap());
+    assert_eq!(cons0.size(), 1);
+    assert!(!cons0.reduce(&cons1).unwrap());
+
+    let cons2 = CompactConstraint::from_iter([[("a", 3)]]);
+    assert_eq!(
+      cons0.reduce(&cons2),
+      Err(DiscreteConflicts {
+        shared: vec!["a"],
+        unsupported: vec![vec![1]],
+        other_unsupported: vec![vec![3]],
+      })
+    );
+    // constraints are unchanged by conflicts
+    assert_eq!(cons0.size(), 1);
+  }
+
+  #[test]
+  fn pop_solution() {
+    let mut cons = CompactConstraint::from_iter([
+      [("a", 1), ("b", 2), ("c", 1)], //
+      [("a", 2), ("b", 2), ("c", 1)],
+    ]);
+    let sltn = cons.pop_solution().unwrap();
+    assert_eq!(
+      HashMap::from_iter(sltn),
+      HashMap::from([("b", 2), ("c", 1)])
+    );
+    assert_eq!(cons.variables().collect::<Vec<_>>(), vec!["a"]);
+    assert_eq!(cons.size(), 2);
+
+    // decompositions share the table, but not the remaining assignments
+    let decompositions: Vec<_> = cons.decompositions().collect();
+    assert_eq!(decompositions.len(), 2);
+    assert!(decompositions.iter().all(|cons| cons.size() == 1));
+    assert_ne!(decompositions[0], decompositions[1]);
+  }
+
+  /// Compact constraints solve to the same solutions as discrete constraints
+  #[test]
+  fn identical_solutions() {
+    let discrete: System<DiscreteConstraint<_, _>> =
+      EDGES.iter().map(|&(a, b)| differ(a, b)).collect();
+    let compact: System<CompactConstraint<_, _>> =
+      EDGES.iter().map(|&(a, b)| differ(a, b)).collect();
+
+    let sltns = solutions(discrete);
+    assert_eq!(sltns.len(), 6);
+    assert_eq!(solutions(compact.clone()), sltns);
+    assert_eq!(
+      solutions(compact.with_propagation(Propagation::Probing)),
+      sltns
+    );
+  }
+
+  #[test]
+  fn arc_consistency() {
+    let mut sys: System<CompactConstraint<_, _>> =
+      EDGES.iter().map(|&(a, b)| differ(a, b)).collect();
+    sys.insert(CompactConstraint::from_iter([[("wa", 0), ("q", 0)]]));
+
+    // with "wa" and "q" the same colour, "nt" and "sa" take the other 2 colours
+    let domains = sys.arc_consistency().unwrap().domains();
+    assert_eq!(domains[&"wa"], HashSet::from([0]));
+    assert_eq!(domains[&"nt"], HashSet::from([1, 2]));
+    assert_eq!(domains[&"sa"], HashSet::from([1, 2]));
+  }
+
+  /// Tables with more assignments than fit in a word
+  #[test]
+  fn large_tables() {
+    // assignments are numbered 10 * a + b, so "a" = 6 and "a" = 9 fall in different words
+    let pairs = CompactConstraint::from_iter(
+      (0..10).flat_map(|a| (0..10).map(move |b| [("a", a), ("b", b)])), //
+    );
+    assert_eq!(pairs.size(), 100);
+
+    // reducing by several shared variables keeps assignments in both words
+    let mut cons = pairs.clone();
+    let other = CompactConstraint::from_iter([
+      [("a", 6), ("b", 3), ("c", 0)],
+      [("a", 6), ("b", 4), ("c", 0)],
+      [("a", 9), ("b", 5), ("c", 1)],
+    ]);
+    assert_eq!(cons.reduce(&other), Ok(true));
+    assert_eq!(cons.size(), 3);
+
+    // reducing by a single shared variable only keeps those in the second word
+    let last = CompactConstraint::from_iter([[("a", 9), ("d", 0)], [("a", 12), ("d", 1)]]);
+    assert_eq!(cons.reduce(&last), Ok(true));
+    assert_eq!(cons.size(), 1);
+    assert_eq!(cons.reduce(&last), Ok(false));
+
+    let mut sys = System::from([pairs, other, last]);
+    let sltn = sys.pop_solution().unwrap();
+    assert_eq!(
+      HashMap::from_iter(sltn),
+      HashMap::from([("a", 9), ("b", 5), ("c", 1), ("d", 0)])
+    );
+  }
+
+  #[test]
+  fn split_on() {
+    let cons = CompactConstraint::from_iter([
+      [("a", 1), ("b", 2)], //
+      [("a", 2), ("b", 1)],
+      [("a", 2), ("b", 2)],
+    ]);
+
+    let mut sizes: Vec<_> = cons
+      .split_on(&"b")
+      .unwrap()
+      .iter()
+      .map(Constraint::size)
+      .collect();
+    sizes.sort();
+    assert_eq!(sizes, vec![1, 2]);
+    assert_eq!(cons.split_on(&"c"), None);
+  }
+
+  #[test]
+  fn branching() {
+    let compact: System<CompactConstraint<_, _>> =
+      EDGES.iter().map(|&(a, b)| differ(a, b)).collect();
+
+    let sltns = solutions(compact.clone());
+    for branching in [Branching::Values, Branching::Binary] {
+      assert_eq!(solutions(compact.clone().with_branching(branching)), sltns);
+    }
+  }
 }
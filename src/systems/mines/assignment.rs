@@ -1,6 +1,6 @@
 //! Assignments for mines in a minesweeper tiles
 
-use std::collections::hash_set;
+use std::collections::{HashSet, hash_set};
 use std::hash::Hash;
 
 use crate::assignment::Assignment;
@@ -8,12 +8,21 @@ use crate::utils::NewHashSet;
 
 /// An assignment of safe / mine tiles in a minesweeper game.\
 /// This keeps track of which tiles are safe and which tiles are mines.
-#[derive(Default, Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct MineAssignment<V: Hash + Eq> {
   safe_tiles: NewHashSet<V>,
   mine_tiles: NewHashSet<V>,
 }
 
+impl<V: Hash + Eq> Default for MineAssignment<V> {
+  fn default() -> Self {
+    Self {
+      safe_tiles: Default::default(),
+      mine_tiles: Default::default(),
+    }
+  }
+}
+
 impl<V: Hash + Eq> FromIterator<(V, bool)> for MineAssignment<V> {
   fn from_iter<T: IntoIterator<Item = (V, bool)>>(iter: T) -> Self {
     let mut safe_tiles = NewHashSet::default();
@@ -67,7 +76,52 @@ impl<V: Hash + Eq> MineAssignment<V> {
   }
 }
 
-impl<V: Hash + Eq> Assignment for MineAssignment<V> {
+impl<V: Hash + Eq + Clone> Assignment for MineAssignment<V> {
+  type Var = V;
+  type Value = bool;
+  /// The contradicting tile, and whether `self` assigned it `true`
+  type Conflict = (V, bool);
+
+  fn get(&self, tile: &V) -> Option<&bool> {
+    if self.safe_tiles.contains(tile) {
+      return Some(&false);
+    }
+    if self.mine_tiles.contains(tile) {
+      return Some(&true);
+    }
+    None
+  }
+
+  fn len(&self) -> usize {
+    self.safe_tiles.len() + self.mine_tiles.len()
+  }
+
+  fn iter(&self) -> impl Iterator<Item = (&V, &bool)> {
+    let safe_tiles = self.safe_tiles.iter().map(|tile| (tile, &false));
+    let mine_tiles = self.mine_tiles.iter().map(|tile| (tile, &true));
+    safe_tiles.chain(mine_tiles)
+  }
+
+  fn insert(&mut self, tile: V, value: bool) -> Option<bool> {
+    let previous = match (self.safe_tiles.remove(&tile), self.mine_tiles.remove(&tile)) {
+      (true, _) => Some(false),
+      (_, true) => Some(true),
+      _ => None,
+    };
+    match value {
+      true => self.mine_tiles.insert(tile),
+      false => self.safe_tiles.insert(tile),
+    };
+    previous
+  }
+
+  fn restrict_to(mut self, vars: impl IntoIterator<Item = V>) -> Self {
+    let vars: HashSet<V> = vars.into_iter().collect();
+    self.safe_tiles.retain(|tile| vars.contains(tile));
+    self.mine_tiles.retain(|tile| vars.contains(tile));
+    self
+  }
+
   fn intersection(mut self, other: Self) -> Self {
     self
       .safe_tiles
@@ -98,6 +152,24 @@ impl<V: Hash + Eq> Assignment for MineAssignment<V> {
 
     self
   }
+
+  fn checked_union(self, other: Self) -> Result<Self, Vec<Self::Conflict>> {
+    let conflicts: Vec<_> = self
+      .safe_tiles
+      .intersection(&other.mine_tiles)
+      .map(|tile| (tile.clone(), false))
+      .chain(
+        self
+          .mine_tiles
+          .intersection(&other.safe_tiles)
+          .map(|tile| (tile.clone(), true)),
+      )
+      .collect();
+    if !conflicts.is_empty() {
+      return Err(conflicts);
+    }
+    Ok(self.union(other))
+  }
 }
 
 impl<V: Hash + Eq> IntoIterator for MineAssignment<V> {
@@ -0,0 +1,217 @@
+//! A minesweeper board that keeps its constraints up to date as tiles are revealed
+
+use std::collections::{HashMap, HashSet};
+use std::fmt::Debug;
+use std::hash::Hash;
+
+use super::{
+  assignment::MineAssignment,
+  constraint::MineConstraint,
+  errors::{MineConflicts, ReductionCase},
+  topology::Topology,
+};
+use crate::{assignment::Assignment, constraint::Constraint, system::System};
+
+/// The state of a minesweeper board whilst it is being played.
+///
+/// The board owns a [`System`] of constraints given by revealed clues.\
+/// Each reveal or flag inserts only the new constraints into the system,\
+/// so only the constraints they overlap need to be minimised again.\
+/// Tiles decided by the system are popped from it and remembered by the board.
+///
+/// ## Examples
+///
+/// ```
+/// # use farc3::systems::mines::board::MineBoard;
+/// # use farc3::systems::mines::topology::Square;
+/// let mut board = MineBoard::new(Square::new(3, 1));
+///
+/// // revealing a 1 at the left end decides the middle tile
+/// let decided = board.reveal((0, 0), 1).unwrap();
+/// assert_eq!(decided, [((0, 0), false), ((1, 0), true)].into());
+/// assert!(board.mines().contains(&(1, 0)));
+/// assert_eq!(board.unknown(), [(2, 0)].into());
+/// ```
+#[derive(Debug, Clone)]
+pub struct MineBoard<T: Topology> {
+  /// The shape of the board
+  topology: T,
+  /// The constraints on tiles that are still unknown
+  system: System<MineConstraint<T::Tile>>,
+  /// The clues shown on revealed tiles
+  revealed: HashMap<T::Tile, usize>,
+  /// Tiles known to be safe, whether revealed or not
+  safe: HashSet<T::Tile>,
+  /// Tiles known to contain mines
+  mines: HashSet<T::Tile>,
+}
+
+impl<T: Topology> MineBoard<T> {
+  /// Constructs a board where no tiles are known
+  pub fn new(topology: T) -> Self {
+    Self {
+      topology,
+      system: System::default(),
+      revealed: HashMap::new(),
+      safe: HashSet::new(),
+      mines: HashSet::new(),
+    }
+  }
+
+  /// The shape of the board
+  pub fn topology(&self) -> &T {
+    &self.topology
+  }
+
+  /// The constraints on tiles that are still unknown
+  pub fn system(&self) -> &System<MineConstraint<T::Tile>> {
+    &self.system
+  }
+
+  /// The clues shown on revealed tiles
+  pub fn revealed(&self) -> &HashMap<T::Tile, usize> {
+    &self.revealed
+  }
+
+  /// Tiles known to be safe, including revealed tiles
+  pub fn safe(&self) -> &HashSet<T::Tile> {
+    &self.safe
+  }
+
+  /// Tiles known to contain mines, including flagged tiles
+  pub fn mines(&self) -> &HashSet<T::Tile> {
+    &self.mines
+  }
+
+  /// Tiles not yet known to be safe or contain a mine
+  pub fn unknown(&self) -> HashSet<T::Tile> {
+    self
+      .topology
+      .tiles()
+      .filter(|tile| !self.safe.contains(tile) && !self.mines.contains(tile))
+      .collect()
+  }
+
+  /// Safe tiles that haven't been revealed yet
+  pub fn unrevealed_safe(&self) -> impl Iterator<Item = &T::Tile> {
+    self
+      .safe
+      .iter()
+      .filter(|tile| !self.revealed.contains_key(tile))
+  }
+
+  /// Reveals a tile, adding the constraint given by its clue
+  ///
+  /// ## Arguments
+  ///
+  /// - `tile`: the tile that was revealed
+  /// - `count`: the number of mines the revealed clue shows
+  ///
+  /// ## Returns
+  ///
+  /// The tiles that became decided by revealing `tile`.\
+  /// If the clue conflicts with what is known, the board should no longer be used.
+  pub fn reveal(
+    &mut self,
+    tile: T::Tile,
+    count: usize,
+  ) -> Result<MineAssignment<T::Tile>, MineConflicts<T::Tile>> {
+    if self.mines.contains(&tile) {
+      // the revealed tile is safe, but is known to be a mine
+      return Err(conflict(
+        MineConstraint::new([tile.clone()], 0),
+        MineConstraint::new([tile], 1),
+      ));
+    }
+    // revealing a tile again can only show the same clue
+    if let Some(&previous) = self.revealed.get(&tile) {
+      if previous == count {
+        return Ok(MineAssignment::default());
+      }
+      return Err(conflict(
+        self.topology.clue(&tile, count),
+        self.topology.clue(&tile, previous),
+      ));
+    }
+
+    // decided tiles have already been popped from the system,
+    // so the clue only constrains the tiles that are still unknown
+    let mut clue = self.topology.clue(&tile, count);
+    let (mines, safe): (Vec<_>, Vec<_>) = clue
+      .variables()
+      .filter(|other| self.mines.contains(other) || self.safe.contains(other))
+      .partition(|other| self.mines.contains(other));
+    clue.reduce(&MineConstraint::new(mines.clone(), mines.len()))?;
+    clue.reduce(&MineConstraint::new(safe, 0))?;
+    self.revealed.insert(tile.clone(), count);
+    self.system.insert(clue);
+
+    let mut decided = MineAssignment::default();
+    if self.safe.insert(tile.clone()) {
+      decided = MineAssignment::all_safe([tile.clone()]);
+      self.system.insert(MineConstraint::new([tile], 0));
+    }
+    Ok(decided.union(self.update()?))
+  }
+
+  /// Flags a tile as containing a mine
+  ///
+  /// ## Returns
+  ///
+  /// The tiles that became decided by flagging `tile`.\
+  /// If the flag conflicts with what is known, the board should no longer be used.
+  pub fn flag(&mut self, tile: T::Tile) -> Result<MineAssignment<T::Tile>, MineConflicts<T::Tile>> {
+    if self.safe.contains(&tile) {
+      // the flagged tile is a mine, but is known to be safe
+      return Err(conflict(
+        MineConstraint::new([tile.clone()], 1),
+        MineConstraint::new([tile], 0),
+      ));
+    }
+    if !self.mines.insert(tile.clone()) {
+      return Ok(MineAssignment::default());
+    }
+
+    self.system.insert(MineConstraint::new([tile.clone()], 1));
+    Ok(MineAssignment::all_mine([tile]).union(self.update()?))
+  }
+
+  /// Pops all newly decided tiles from the system and remembers them
+  fn update(&mut self) -> Result<MineAssignment<T::Tile>, MineConflicts<T::Tile>> {
+    let mut decided = vec![];
+    for (tile, mine) in self.system.pop_solution()? {
+      let (known, other) = match mine {
+        true => (&mut self.mines, &self.safe),
+        false => (&mut self.safe, &self.mines),
+      };
+      if other.contains(&tile) {
+        return Err(conflict(
+          MineConstraint::new([tile.clone()], mine as usize),
+          MineConstraint::new([tile], !mine as usize),
+        ));
+      }
+      if known.insert(tile.clone()) {
+        decided.push((tile, mine));
+      }
+    }
+    Ok(decided.into_iter().collect())
+  }
+}
+
+/// The conflict produced when `constraint` can't be reduced by `other`,\
+/// classified by the case that [`MineConstraint::reduce`] would try
+///
+/// [`MineConstraint::reduce`]: crate::constraint::Constraint::reduce
+fn conflict<V: Hash + Eq + Clone>(
+  constraint: MineConstraint<V>,
+  other: MineConstraint<V>,
+) -> MineConflicts<V> {
+  let case = if other.count() == 0 {
+    ReductionCase::AllSafe
+  } else if other.count() == other.tiles().len() {
+    ReductionCase::AllMine
+  } else {
+    ReductionCase::Subset
+  };
+  MineConflicts::reduction(&constraint, &other, case)
+}
@@ -1,10 +1,19 @@
 //! Constraints for mine sweeper solving
 
+use std::collections::HashSet;
+use std::fmt::{self, Debug, Display};
 use std::hash::Hash;
 use std::mem;
 
-use super::{assignment::MineAssignment, errors::MineConflicts, utils::choose_num};
-use crate::{constraint::Constraint, utils::NewHashSet};
+use super::{
+  assignment::MineAssignment,
+  errors::{MineConflicts, ReductionCase},
+  utils::choose_num,
+};
+use crate::{
+  assignment::Assignment, constraint::Constraint, systems::generic::constraint::DiscreteConstraint,
+  utils::NewHashSet,
+};
 
 /// A constraint for the number of mines present in the given tiles.
 #[derive(Default, Debug, Hash, PartialEq, Eq, Clone)]
@@ -47,12 +56,28 @@ impl<V: Hash + Eq> MineConstraint<V> {
       count,
     }
   }
+
+  /// The tiles that mines could be present in
+  pub fn tiles(&self) -> &HashSet<V> {
+    &self.tiles
+  }
+
+  /// The number of mines present in the constraint's tiles
+  pub fn count(&self) -> usize {
+    self.count
+  }
+}
+
+impl<V: Hash + Eq + Debug> Display for MineConstraint<V> {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{} mines among {:?}", self.count, &*self.tiles)
+  }
 }
 
 impl<V: Hash + Eq + Clone> Constraint for MineConstraint<V> {
   type Var = V;
   type Solution = MineAssignment<V>;
-  type ConflictErr = MineConflicts;
+  type ConflictErr = MineConflicts<V>;
 
   fn size(&self) -> usize {
     choose_num(self.tiles.len(), self.count)
@@ -62,26 +87,13 @@ impl<V: Hash + Eq + Clone> Constraint for MineConstraint<V> {
     self.tiles.iter().cloned()
   }
 
+  /// Decomposes on whether a single tile is a mine
   fn decompositions(&self) -> impl Iterator<Item = Self> {
-    self.tiles.iter().flat_map(|tile| {
-      let tiles = NewHashSet::from([tile.clone()]);
-
-      let mut assigns = vec![];
-      if self.count > 0 {
-        assigns.push(Self {
-          tiles: tiles.clone(),
-          count: 1,
-        })
-      }
-      if self.count < self.tiles.len() {
-        assigns.push(Self {
-          tiles: tiles.clone(),
-          count: 0,
-        })
-      }
-
-      assigns.into_iter()
-    })
+    let tile = self.tiles.iter().next();
+    tile
+      .and_then(|tile| self.split_on(tile))
+      .into_iter()
+      .flatten()
   }
 
   fn reduce(&mut self, other: &Self) -> Result<bool, Self::ConflictErr> {
@@ -92,7 +104,11 @@ impl<V: Hash + Eq + Clone> Constraint for MineConstraint<V> {
     if other.count == 0 {
       // conflict when reduction would give us more mines than tiles
       if tiles.len() < self.count {
-        return Err(MineConflicts);
+        return Err(MineConflicts::reduction(
+          self,
+          other,
+          ReductionCase::AllSafe,
+        ));
       }
 
       self.tiles = tiles;
@@ -105,7 +121,11 @@ impl<V: Hash + Eq + Clone> Constraint for MineConstraint<V> {
 
       // conflict when reduction would give us less than 0 mines
       if self.count < len_overlap {
-        return Err(MineConflicts);
+        return Err(MineConflicts::reduction(
+          self,
+          other,
+          ReductionCase::AllMine,
+        ));
       }
 
       self.count -= len_overlap;
@@ -117,7 +137,7 @@ impl<V: Hash + Eq + Clone> Constraint for MineConstraint<V> {
     if other.tiles.is_subset(&self.tiles) {
       // conflict on either < 0 or > len number of mines
       if (self.count < other.count) || (tiles.len() < self.count - other.count) {
-        return Err(MineConflicts);
+        return Err(MineConflicts::reduction(self, other, ReductionCase::Subset));
       }
 
       self.count -= other.count;
@@ -142,4 +162,118 @@ impl<V: Hash + Eq + Clone> Constraint for MineConstraint<V> {
 
     None
   }
+
+  fn is_satisfied(&self, solution: &Self::Solution) -> bool {
+    let mut mines = 0;
+    for tile in self.tiles.iter() {
+      match solution.get(tile) {
+        Some(&mine) => mines += mine as usize,
+        None => return false,
+      }
+    }
+    mines == self.count
+  }
+
+  /// Excluding a decomposition assigns the opposite value to its tile
+  fn exclude(&self, decomposition: &Self) -> Option<Self> {
+    if decomposition.tiles.len() != 1 {
+      return None;
+    }
+    Some(Self {
+      tiles: decomposition.tiles.clone(),
+      count: 1 - decomposition.count,
+    })
+  }
+
+  fn split_on(&self, tile: &V) -> Option<Vec<Self>> {
+    if !self.tiles.contains(tile) {
+      return None;
+    }
+
+    let tiles = NewHashSet::from([tile.clone()]);
+    let mut splits = vec![];
+    if self.count > 0 {
+      splits.push(Self {
+        tiles: tiles.clone(),
+        count: 1,
+      })
+    }
+    if self.count < self.tiles.len() {
+      splits.push(Self { tiles, count: 0 })
+    }
+    Some(splits)
+  }
+
+  fn subsumes(&self, other: &Self) -> bool {
+    if !other.tiles.is_subset(&self.tiles) {
+      return false;
+    }
+    // when all tiles are decided, any subset is decided the same way
+    match (self.count, other.count) {
+      (0, 0) => true,
+      (count, mines) if count == self.tiles.len() => mines == other.tiles.len(),
+      (count, mines) => count == mines && other.tiles.len() == self.tiles.len(),
+    }
+  }
+}
+
+/// Lowers a mine constraint into a table of every placement of its mines
+impl<V: Hash + Eq + Clone> From<MineConstraint<V>> for DiscreteConstraint<V, bool> {
+  fn from(value: MineConstraint<V>) -> Self {
+    let variables: Vec<V> = value.tiles().iter().cloned().collect();
+    let (len, count) = (variables.len(), value.count());
+    if len < count {
+      return Self::from_table(variables, []);
+    }
+
+    // place mines one tile at a time, whilst enough tiles remain for the rest
+    let mut placements = vec![vec![]];
+    for idx in 0..len {
+      let remaining = len - idx - 1;
+      placements = placements
+        .into_iter()
+        .flat_map(|values: Vec<bool>| {
+          [false, true].map(|mine| {
+            let mut values = values.clone();
+            values.push(mine);
+            values
+          })
+        })
+        .filter(|values| {
+          let mines = values.iter().filter(|&&mine| mine).count();
+          mines <= count && count <= mines + remaining
+        })
+        .collect();
+    }
+
+    Self::from_table(variables, placements)
+  }
+}
+
+/// Raises a table into a mine constraint,\
+/// when the table allows every placement of some number of mines.
+///
+/// ## Returns
+///
+/// The mine constraint, or the original table if it can't be represented,\
+/// such as a table with no placements at all
+impl<V: Hash + Eq + Clone> TryFrom<DiscreteConstraint<V, bool>> for MineConstraint<V> {
+  type Error = DiscreteConstraint<V, bool>;
+
+  fn try_from(value: DiscreteConstraint<V, bool>) -> Result<Self, Self::Error> {
+    let (variables, rows) = value.table();
+    let len = variables.len();
+    let counts: Vec<_> = rows
+      .map(|values| values.iter().filter(|&&mine| mine).count())
+      .collect();
+    let Some(&count) = counts.first() else {
+      return Err(value);
+    };
+
+    // distinct placements of the same number of mines are all of them when there's enough
+    if counts.iter().any(|&other| other != count) || counts.len() != choose_num(len, count) {
+      return Err(value);
+    }
+    Ok(MineConstraint::new(variables.to_vec(), count))
+  }
 }
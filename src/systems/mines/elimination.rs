@@ -0,0 +1,205 @@
+//! Gaussian elimination over the linear equations given by mine constraints
+
+use std::collections::{BTreeSet, HashMap};
+use std::fmt::Debug;
+use std::hash::Hash;
+
+use super::{constraint::MineConstraint, errors::MineConflicts};
+use crate::{constraint::Constraint, system::System};
+
+/// A linear equation `coeffs · tiles = total`, where every tile is 0 or 1
+#[derive(Debug, Clone)]
+struct Row {
+  coeffs: Vec<i64>,
+  total: i64,
+  /// The indexes of the constraints combined into this row
+  sources: BTreeSet<usize>,
+}
+
+impl Row {
+  /// Subtracts a multiple of `pivot` that zeroes this row at column `col`.
+  ///
+  /// Rows are combined with 128 bit integers and divided through by the gcd of their entries,\
+  /// keeping coefficients small throughout elimination.\
+  /// A row that would still overflow is left as is, which is still a valid equation.
+  fn eliminate(&mut self, pivot: &Row, col: usize) {
+    let (scale, factor) = (pivot.coeffs[col], self.coeffs[col]);
+    if factor == 0 {
+      return;
+    }
+    // only scale each row as much as is needed to cancel the column
+    let divisor = gcd(scale.unsigned_abs().into(), factor.unsigned_abs().into()) as i64;
+    let (scale, factor) = ((scale / divisor) as i128, (factor / divisor) as i128);
+
+    let combine = |own: i64, other: i64| (own as i128) * scale - (other as i128) * factor;
+    let coeffs: Vec<i128> = self
+      .coeffs
+      .iter()
+      .zip(&pivot.coeffs)
+      .map(|(&own, &other)| combine(own, other))
+      .collect();
+    let total = combine(self.total, pivot.total);
+
+    let divisor = coeffs
+      .iter()
+      .fold(total.unsigned_abs(), |acc, &coeff| {
+        gcd(acc, coeff.unsigned_abs())
+      })
+      .max(1) as i128;
+    let narrow = |value: i128| i64::try_from(value / divisor).ok();
+    let Some(coeffs) = coeffs.into_iter().map(narrow).collect::<Option<Vec<_>>>() else {
+      return;
+    };
+    let Some(total) = narrow(total) else {
+      return;
+    };
+
+    self.coeffs = coeffs;
+    self.total = total;
+    self.sources.extend(&pivot.sources);
+  }
+
+  /// The values of tiles that every 0/1 solution to this row agrees on
+  ///
+  /// ## Returns
+  ///
+  /// Pairs of column indexes and whether that tile is a mine,\
+  /// or `None` if no 0/1 solution exists.
+  fn forced(&self) -> Option<Vec<(usize, bool)>> {
+    // the smallest and largest totals possible from 0/1 tiles,
+    // summed with 128 bit integers as many large coefficients can overflow
+    let total = self.total as i128;
+    let min: i128 = self
+      .coeffs
+      .iter()
+      .filter(|&&c| c < 0)
+      .map(|&c| c as i128)
+      .sum();
+    let max: i128 = self
+      .coeffs
+      .iter()
+      .filter(|&&c| c > 0)
+      .map(|&c| c as i128)
+      .sum();
+    if total < min || max < total {
+      return None;
+    }
+
+    let forced = self.coeffs.iter().enumerate().filter_map(|(col, &coeff)| {
+      let coeff = coeff as i128;
+      // the total can't be reached without this tile taking a given value
+      if coeff > 0 && total > max - coeff || coeff < 0 && total < min - coeff {
+        Some((col, true))
+      } else if coeff > 0 && total < min + coeff || coeff < 0 && total > max + coeff {
+        Some((col, false))
+      } else {
+        None
+      }
+    });
+    Some(forced.collect())
+  }
+}
+
+/// The greatest common divisor of 2 non-negative integers
+fn gcd(a: u128, b: u128) -> u128 {
+  match b {
+    0 => a,
+    _ => gcd(b, a % b),
+  }
+}
+
+impl<V: Hash + Eq + Clone + Debug> System<MineConstraint<V>> {
+  /// Finds tiles forced by combining all constraints at once.
+  ///
+  /// The constraints are treated as linear equations over the integers\
+  /// and row reduced with Gaussian elimination.\
+  /// Each reduced row is then bounded using the fact that tiles are either 0 or 1,\
+  /// which can force tiles that reducing pairs of constraints can't.
+  ///
+  /// Forced tiles are inserted as decided constraints,\
+  /// to be popped by the next call to [`System::pop_solution`].
+  ///
+  /// ## Returns
+  ///
+  /// A mutable reference to allow method chaining
+  ///
+  /// ## Examples
+  ///
+  /// ```
+  /// # use std::collections::HashMap;
+  /// # use farc3::prelude::*;
+  /// // no constraint is a subset of another, so reducing pairs decides nothing
+  /// let mut sys = System::from([
+  ///   MineConstraint::new([0, 1], 1),
+  ///   MineConstraint::new([1, 2], 1),
+  ///   MineConstraint::new([0, 2, 3], 2),
+  /// ]);
+  ///
+  /// // but tile 1 being a mine would need 2 mines in tile 3
+  /// let sltn = sys.eliminate().unwrap().pop_solution().unwrap();
+  /// assert_eq!(
+  ///   HashMap::from_iter(sltn),
+  ///   HashMap::from([(0, true), (1, false), (2, true), (3, false)])
+  /// );
+  /// ```
+  pub fn eliminate(&mut self) -> Result<&mut Self, MineConflicts<V>> {
+    let mut columns: HashMap<V, usize> = HashMap::new();
+    let mut tiles = vec![];
+    for tile in self.iter().flat_map(Constraint::variables) {
+      columns.entry(tile.clone()).or_insert_with(|| {
+        tiles.push(tile);
+        tiles.len() - 1
+      });
+    }
+
+    let mut rows: Vec<Row> = self
+      .iter()
+      .enumerate()
+      .map(|(idx, cons)| {
+        let mut coeffs = vec![0; tiles.len()];
+        for tile in cons.variables() {
+          coeffs[columns[&tile]] = 1;
+        }
+        Row {
+          coeffs,
+          total: cons.count() as i64,
+          sources: BTreeSet::from([idx]),
+        }
+      })
+      .collect();
+
+    // reduce to row echelon form, eliminating each pivot from all other rows
+    let mut pivot_row = 0;
+    for col in 0..tiles.len() {
+      let Some(idx) = (pivot_row..rows.len()).find(|&idx| rows[idx].coeffs[col] != 0) else {
+        continue;
+      };
+      rows.swap(pivot_row, idx);
+
+      let pivot = rows[pivot_row].clone();
+      for (idx, row) in rows.iter_mut().enumerate() {
+        if idx != pivot_row {
+          row.eliminate(&pivot, col);
+        }
+      }
+      pivot_row += 1;
+    }
+
+    for row in &rows {
+      let Some(forced) = row.forced() else {
+        let constraints: Vec<_> = self.iter().cloned().collect();
+        return Err(MineConflicts::Elimination {
+          constraints: row
+            .sources
+            .iter()
+            .map(|&idx| constraints[idx].clone())
+            .collect(),
+        });
+      };
+      for (col, mine) in forced {
+        self.insert(MineConstraint::new([tiles[col].clone()], mine as usize));
+      }
+    }
+    Ok(self)
+  }
+}
@@ -0,0 +1,230 @@
+//! Exhaustive search for the best move in a minesweeper endgame
+
+use std::collections::{HashMap, HashSet};
+use std::fmt::Debug;
+use std::hash::Hash;
+
+use super::constraint::MineConstraint;
+use crate::{constraint::Constraint, system::System, utils::NewHashSet};
+
+/// The tiles revealed so far in a search, alongside their clues
+type Revealed<V> = NewHashSet<(V, usize)>;
+
+/// The best tile to reveal and the chance of winning for each position reached in a search
+type Memo<V> = HashMap<Revealed<V>, (Option<V>, f64)>;
+
+/// The maximum number of unknown tiles that an endgame can be searched for.\
+/// The search is exponential in the number of unknown tiles, so this should be kept small.\
+/// Positions reached by revealing the same clues in a different order are only searched once.
+pub const MAX_TILES: usize = 12;
+
+/// The best tile to reveal in an endgame, along with the chance of winning after revealing it
+#[derive(Debug, Clone, PartialEq)]
+pub struct EndgameMove<V> {
+  /// The tile to reveal
+  pub tile: V,
+  /// The probability of winning when revealing `tile` and playing perfectly afterwards
+  pub win_chance: f64,
+}
+
+/// Searches every sequence of reveals in an endgame,\
+/// to find the move that maximises the chance of winning.
+///
+/// The chance of winning after revealing a tile is the sum over each clue it could reveal,\
+/// of the chance of that clue multiplied by the chance of winning from the resulting position.
+///
+/// ## Note
+///
+/// Unlike picking the tile least likely to be a mine,\
+/// this accounts for how useful the clue under a tile is for later moves.
+///
+/// ## Examples
+///
+/// ```
+/// # use farc3::prelude::*;
+/// # use farc3::systems::mines::endgame::EndgameSolver;
+/// // 1 mine among 3 tiles in a line, with no clues
+/// let sys = System::default();
+/// let solver = EndgameSolver::new(sys, 0..3, 1, |&tile: &i32| [tile - 1, tile + 1]);
+///
+/// // revealing an end tile tells us whether the middle tile is a mine
+/// let best = solver.solve().unwrap();
+/// assert_ne!(best.tile, 1);
+/// assert!((best.win_chance - 2.0 / 3.0).abs() < 1e-9);
+/// ```
+pub struct EndgameSolver<V: Hash + Eq + Clone + Debug, N> {
+  /// The constraints given by revealed clues
+  system: System<MineConstraint<V>>,
+  /// All tiles that are neither revealed nor flagged
+  unknown: HashSet<V>,
+  /// The number of mines left among `unknown` tiles
+  mines: usize,
+  /// The tiles that a revealed tile would give a clue for
+  neighbours: N,
+}
+
+impl<V, N, I> EndgameSolver<V, N>
+where
+  V: Hash + Eq + Clone + Debug,
+  N: Fn(&V) -> I,
+  I: IntoIterator<Item = V>,
+{
+  /// Constructs an endgame solver for a partially revealed board
+  ///
+  /// ## Arguments
+  ///
+  /// - `system`: the constraints given by revealed clues
+  /// - `unknown`: all tiles that are neither revealed nor flagged
+  /// - `mines`: the number of mines left among `unknown` tiles
+  /// - `neighbours`: the tiles that a clue for a given tile would count mines in
+  ///
+  /// ## Returns
+  ///
+  /// A new [`EndgameSolver`]
+  pub fn new(
+    system: System<MineConstraint<V>>,
+    unknown: impl IntoIterator<Item = V>,
+    mines: usize,
+    neighbours: N,
+  ) -> Self {
+    let mut unknown: HashSet<V> = unknown.into_iter().collect();
+    unknown.extend((&system).into_iter().flat_map(|cons| cons.variables()));
+
+    Self {
+      system,
+      unknown,
+      mines,
+      neighbours,
+    }
+  }
+
+  /// Finds the move with the highest chance of winning
+  ///
+  /// ## Returns
+  ///
+  /// The best move to make, or `None` when:
+  /// - there are more than [`MAX_TILES`] unknown tiles
+  /// - no placement of mines is consistent with the board
+  /// - every safe tile has already been revealed
+  pub fn solve(&self) -> Option<EndgameMove<V>> {
+    if self.unknown.len() > MAX_TILES {
+      return None;
+    }
+
+    let mut system = self.system.clone();
+    system.insert(MineConstraint::new(
+      self.unknown.iter().cloned(),
+      self.mines,
+    ));
+    let worlds = mine_worlds(system.clone());
+
+    let revealed = Revealed::default();
+    let mut memo = Memo::new();
+    let (tile, win_chance) = self.search(&system, &self.unknown, &worlds, &revealed, &mut memo);
+    Some(EndgameMove {
+      tile: tile?,
+      win_chance,
+    })
+  }
+
+  /// Finds the best tile to reveal and the chance of winning after revealing it
+  ///
+  /// ## Arguments
+  ///
+  /// - `system`: the constraints on the board, including the total mine count
+  /// - `unknown`: all tiles that haven't been revealed
+  /// - `worlds`: the mine tiles in each solution to `system`
+  /// - `revealed`: the tiles revealed so far in the search, alongside their clues
+  /// - `memo`: the results for positions that have already been searched
+  fn search(
+    &self,
+    system: &System<MineConstraint<V>>,
+    unknown: &HashSet<V>,
+    worlds: &[HashSet<V>],
+    revealed: &Revealed<V>,
+    memo: &mut Memo<V>,
+  ) -> (Option<V>, f64) {
+    if let Some(result) = memo.get(revealed) {
+      return result.clone();
+    }
+
+    let Some(world) = worlds.first() else {
+      return (None, 0.0);
+    };
+    // the game is won once only mines are left unrevealed
+    if world.len() == unknown.len() {
+      return (None, 1.0);
+    }
+
+    // revealing a tile that's safe in every world never lowers the chance of winning,
+    // as any other strategy will have to reveal that tile at some point
+    let safe = unknown
+      .iter()
+      .find(|tile| worlds.iter().all(|world| !world.contains(*tile)));
+    let candidates: Vec<&V> = match safe {
+      Some(tile) => vec![tile],
+      None => unknown.iter().collect(),
+    };
+
+    let result = candidates
+      .into_iter()
+      .map(|tile| {
+        let chance = self.reveal_chance(system, unknown, worlds, revealed, memo, tile);
+        (Some(tile.clone()), chance)
+      })
+      .max_by(|(_, chance0), (_, chance1)| chance0.total_cmp(chance1))
+      .unwrap_or((None, 0.0));
+
+    memo.insert(revealed.clone(), result.clone());
+    result
+  }
+
+  /// The chance of winning after revealing `tile` and playing perfectly afterwards
+  fn reveal_chance(
+    &self,
+    system: &System<MineConstraint<V>>,
+    unknown: &HashSet<V>,
+    worlds: &[HashSet<V>],
+    revealed: &Revealed<V>,
+    memo: &mut Memo<V>,
+    tile: &V,
+  ) -> f64 {
+    let neighbours: Vec<V> = (self.neighbours)(tile)
+      .into_iter()
+      .filter(|other| other != tile && unknown.contains(other))
+      .collect();
+    let mut rest = unknown.clone();
+    rest.remove(tile);
+
+    let mut chance = 0.0;
+    for clue in 0..=neighbours.len() {
+      let mut sys = system.clone();
+      sys.insert(MineConstraint::new([tile.clone()], 0));
+      sys.insert(MineConstraint::new(neighbours.iter().cloned(), clue));
+
+      let outcomes = mine_worlds(sys.clone());
+      if outcomes.is_empty() {
+        continue;
+      }
+
+      let mut revealed = revealed.clone();
+      revealed.insert((tile.clone(), clue));
+      let (_, win_chance) = self.search(&sys, &rest, &outcomes, &revealed, memo);
+      chance += win_chance * (outcomes.len() as f64) / (worlds.len() as f64);
+    }
+    chance
+  }
+}
+
+/// The mine tiles in each solution to a system
+fn mine_worlds<V: Hash + Eq + Clone + Debug>(system: System<MineConstraint<V>>) -> Vec<HashSet<V>> {
+  system
+    .solve()
+    .map(|sltn| {
+      sltn
+        .into_iter()
+        .filter_map(|(tile, mine)| mine.then_some(tile))
+        .collect()
+    })
+    .collect()
+}
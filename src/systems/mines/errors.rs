@@ -1,23 +1,181 @@
 //! Errors produced when mine constraints conflict
 
-/// The error that is produced when 2 mine constraints conflict.
+use std::collections::HashSet;
+use std::error::Error;
+use std::fmt::{self, Debug, Display};
+use std::hash::Hash;
+
+use super::constraint::MineConstraint;
+
+/// The ways that one mine constraint can reduce another,\
+/// as done by [`MineConstraint::reduce`].
+///
+/// [`MineConstraint::reduce`]: crate::constraint::Constraint::reduce
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ReductionCase {
+  /// The other constraint has no mines, so its tiles are removed
+  AllSafe,
+  /// The other constraint is all mines, so its tiles and mines are removed
+  AllMine,
+  /// The other constraint's tiles are a subset, so its tiles and mines are removed
+  Subset,
+}
+
+impl Display for ReductionCase {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Self::AllSafe => write!(f, "all safe"),
+      Self::AllMine => write!(f, "all mines"),
+      Self::Subset => write!(f, "subset"),
+    }
+  }
+}
+
+/// The error that is produced when mine constraints conflict.
 ///
 /// ## Example
 ///
 /// ```
-/// # use farc3_csp::constraint::Constraint;
-/// # use farc3_csp::systems::mines::{
+/// # use std::collections::HashSet;
+/// # use farc3::constraint::Constraint;
+/// # use farc3::systems::mines::{
 /// #   constraint::MineConstraint,
-/// #   errors::MineConflicts
+/// #   errors::{MineConflicts, ReductionCase}
 /// # };
 ///
 /// let mut cons0 = MineConstraint::new([0, 1], 1);
 /// let cons1 = MineConstraint::new([0, 1], 2);
 ///
-/// let res = cons0.reduce(&cons1);
-/// assert_eq!(res, Err(MineConflicts));
+/// let err = cons0.reduce(&cons1).unwrap_err();
+/// assert_eq!(
+///   err,
+///   MineConflicts::Reduction {
+///     constraint: cons0.clone(),
+///     other: cons1,
+///     case: ReductionCase::AllMine,
+///   }
+/// );
+/// assert_eq!(err.overlap(), HashSet::from([&0, &1]));
 /// ```
-///
-/// @todo provide better debug info on mine conflicts
-#[derive(Debug, PartialEq, Eq)]
-pub struct MineConflicts;
+#[derive(Clone, PartialEq, Eq)]
+pub enum MineConflicts<V: Hash + Eq> {
+  /// Reducing one constraint by another would leave no valid placement of mines
+  Reduction {
+    /// The constraint being reduced
+    constraint: MineConstraint<V>,
+    /// The constraint it was reduced by
+    other: MineConstraint<V>,
+    /// The reduction that failed
+    case: ReductionCase,
+  },
+  /// Constraints were combined by Gaussian elimination\
+  /// into an equation that no placement of mines satisfies
+  Elimination {
+    /// The constraints that were combined
+    constraints: Vec<MineConstraint<V>>,
+  },
+}
+
+impl<V: Hash + Eq> MineConflicts<V> {
+  /// Constructs the conflict produced when reducing `constraint` by `other` fails
+  pub(crate) fn reduction(
+    constraint: &MineConstraint<V>,
+    other: &MineConstraint<V>,
+    case: ReductionCase,
+  ) -> Self
+  where
+    V: Clone,
+  {
+    Self::Reduction {
+      constraint: constraint.clone(),
+      other: other.clone(),
+      case,
+    }
+  }
+
+  /// The constraints involved in the conflict
+  pub fn constraints(&self) -> Vec<&MineConstraint<V>> {
+    match self {
+      Self::Reduction {
+        constraint, other, ..
+      } => vec![constraint, other],
+      Self::Elimination { constraints } => constraints.iter().collect(),
+    }
+  }
+
+  /// The tiles shared by more than one of the constraints involved in the conflict
+  pub fn overlap(&self) -> HashSet<&V> {
+    let mut seen = HashSet::new();
+    let mut overlap = HashSet::new();
+    for tile in self.constraints().into_iter().flat_map(|cons| cons.tiles()) {
+      if !seen.insert(tile) {
+        overlap.insert(tile);
+      }
+    }
+    overlap
+  }
+}
+
+/// Only the sizes and mine counts of the constraints are shown,\
+/// so that tiles don't need to implement `Debug`.\
+/// The [`Display`] implementation shows the tiles themselves.
+impl<V: Hash + Eq> Debug for MineConflicts<V> {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Self::Reduction {
+        constraint,
+        other,
+        case,
+      } => f
+        .debug_struct("Reduction")
+        .field("constraint", &counts(constraint))
+        .field("other", &counts(other))
+        .field("case", case)
+        .finish(),
+      Self::Elimination { constraints } => {
+        let constraints: Vec<_> = constraints.iter().map(counts).collect();
+        f.debug_struct("Elimination")
+          .field("constraints", &constraints)
+          .finish()
+      }
+    }
+  }
+}
+
+impl<V: Hash + Eq + Debug> Display for MineConflicts<V> {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Self::Reduction {
+        constraint,
+        other,
+        case,
+      } => write!(
+        f,
+        "cannot reduce {constraint} by {other} ({case}), as they overlap on {:?}",
+        self.overlap()
+      ),
+      Self::Elimination { constraints } => {
+        write!(f, "no placement of mines satisfies all of ")?;
+        for (idx, constraint) in constraints.iter().enumerate() {
+          if idx > 0 {
+            write!(f, ", ")?;
+          }
+          write!(f, "{constraint}")?;
+        }
+        Ok(())
+      }
+    }
+  }
+}
+
+impl<V: Hash + Eq + Debug> Error for MineConflicts<V> {}
+
+/// Shows the number of tiles and mines in a constraint, without its tiles
+fn counts<V: Hash + Eq>(constraint: &MineConstraint<V>) -> impl Debug + '_ {
+  fmt::from_fn(move |f| {
+    f.debug_struct("MineConstraint")
+      .field("tiles", &constraint.tiles().len())
+      .field("count", &constraint.count())
+      .finish()
+  })
+}
@@ -0,0 +1,305 @@
+//! A JSON schema for minesweeper boards.
+//!
+//! Boards are stored as an object with the fields:
+//!
+//! - `width`: the number of columns on the board
+//! - `height`: the number of rows on the board
+//! - `mines`: a list of `[x, y]` positions of mines
+//! - `revealed`: an optional list of `[x, y]` positions of revealed tiles
+//!
+//! Any other fields are ignored when reading.
+//!
+//! ## Examples
+//!
+//! ```
+//! # use farc3::systems::mines::formats::json;
+//! let input = r#"{"width": 3, "height": 1, "mines": [[2, 0]], "revealed": [[1, 0]]}"#;
+//! let game = json::read(input).unwrap();
+//!
+//! assert!(game.layout.is_mine(&(2, 0)));
+//! assert!(game.revealed.contains(&(1, 0)));
+//! assert_eq!(
+//!   json::write(&game),
+//!   r#"{"width":3,"height":1,"mines":[[2,0]],"revealed":[[1,0]]}"#
+//! );
+//! ```
+
+use super::{FormatError, Game, check_bounds};
+use crate::systems::mines::generator::{Layout, Tile};
+
+/// The deepest that arrays and objects can be nested.\
+/// The schema only needs 3, the rest leaves room for fields that are ignored.
+const MAX_DEPTH: usize = 16;
+
+/// The most tiles a board can have
+const MAX_AREA: usize = 1 << 24;
+
+/// Reads a game from a JSON board
+///
+/// ## Errors
+///
+/// - [`FormatError::InvalidJson`] for malformed JSON, missing fields or deeply nested values
+/// - [`FormatError::TooLarge`] when the board has more than 2^24 tiles
+/// - [`FormatError::OutOfBounds`] when a tile is placed outside of the board
+/// - [`FormatError::RevealedMine`] when a revealed tile contains a mine
+pub fn read(input: &str) -> Result<Game, FormatError> {
+  let mut parser = Parser {
+    input,
+    position: 0,
+    depth: 0,
+  };
+  let start = parser.skip_whitespace();
+  let value = parser.value()?;
+  let end = parser.skip_whitespace();
+  if end < input.len() {
+    return Err(FormatError::TrailingData { position: end });
+  }
+
+  let Value::Object(fields) = value else {
+    return Err(parser.expected_at(start, "a board object"));
+  };
+  let field = |name| {
+    fields
+      .iter()
+      .find(|(key, _)| key == name)
+      .map(|(_, value)| value)
+  };
+  let required = |name, expected| field(name).ok_or(parser.expected_at(start, expected));
+
+  let width = required("width", "a `width` field")?.as_usize(start, "an integer width")?;
+  let height = required("height", "a `height` field")?.as_usize(start, "an integer height")?;
+  if width.checked_mul(height).is_none_or(|area| area > MAX_AREA) {
+    return Err(FormatError::TooLarge);
+  }
+  let mines = required("mines", "a `mines` field")?.as_tiles(start)?;
+  let revealed = match field("revealed") {
+    Some(value) => value.as_tiles(start)?,
+    None => vec![],
+  };
+
+  let layout = Layout::new(width, height, mines.iter().copied());
+  for &tile in &mines {
+    check_bounds(&layout, tile)?;
+  }
+  Game::new(layout, revealed)
+}
+
+/// Writes a game as a compact JSON board, listing tiles in row order
+pub fn write(game: &Game) -> String {
+  let layout = &game.layout;
+  let tiles = |tiles: &mut dyn Iterator<Item = &Tile>| {
+    let mut tiles: Vec<_> = tiles.collect();
+    tiles.sort_by_key(|&&(x, y)| (y, x));
+    let tiles: Vec<_> = tiles.iter().map(|(x, y)| format!("[{x},{y}]")).collect();
+    tiles.join(",")
+  };
+
+  format!(
+    r#"{{"width":{},"height":{},"mines":[{}],"revealed":[{}]}}"#,
+    layout.width(),
+    layout.height(),
+    tiles(&mut layout.mines().iter()),
+    tiles(&mut game.revealed.iter()),
+  )
+}
+
+/// A parsed JSON value
+#[derive(Debug)]
+enum Value<'a> {
+  /// A null, boolean or string, which the schema never needs the contents of
+  Other,
+  /// A number, kept as written so that integers aren't rounded
+  Number(&'a str),
+  Array(Vec<Value<'a>>),
+  Object(Vec<(String, Value<'a>)>),
+}
+
+impl Value<'_> {
+  /// Interprets the value as a non-negative integer
+  fn as_usize(&self, position: usize, expected: &'static str) -> Result<usize, FormatError> {
+    match self {
+      Self::Number(number) => number.parse().ok(),
+      _ => None,
+    }
+    .ok_or(FormatError::InvalidJson { position, expected })
+  }
+
+  /// Interprets the value as a list of `[x, y]` positions
+  fn as_tiles(&self, position: usize) -> Result<Vec<Tile>, FormatError> {
+    let expected = "a list of [x, y] positions";
+    let Self::Array(tiles) = self else {
+      return Err(FormatError::InvalidJson { position, expected });
+    };
+
+    tiles
+      .iter()
+      .map(|tile| match tile {
+        Self::Array(coords) if coords.len() == 2 => Ok((
+          coords[0].as_usize(position, expected)?,
+          coords[1].as_usize(position, expected)?,
+        )),
+        _ => Err(FormatError::InvalidJson { position, expected }),
+      })
+      .collect()
+  }
+}
+
+/// A minimal recursive descent parser for JSON values
+struct Parser<'a> {
+  input: &'a str,
+  position: usize,
+  /// The number of arrays and objects the parser is currently inside
+  depth: usize,
+}
+
+impl<'a> Parser<'a> {
+  fn expected_at(&self, position: usize, expected: &'static str) -> FormatError {
+    match position < self.input.len() {
+      true => FormatError::InvalidJson { position, expected },
+      false => FormatError::UnexpectedEnd,
+    }
+  }
+
+  fn expected(&self, expected: &'static str) -> FormatError {
+    self.expected_at(self.position, expected)
+  }
+
+  fn peek(&self) -> Option<u8> {
+    self.input.as_bytes().get(self.position).copied()
+  }
+
+  /// Skips past whitespace, returning the position of the next token
+  fn skip_whitespace(&mut self) -> usize {
+    while let Some(b' ' | b'\t' | b'\n' | b'\r') = self.peek() {
+      self.position += 1;
+    }
+    self.position
+  }
+
+  /// Consumes `token` if it's next in the input
+  fn eat(&mut self, token: &str) -> bool {
+    let matches = self.input[self.position..].starts_with(token);
+    if matches {
+      self.position += token.len();
+    }
+    matches
+  }
+
+  fn value(&mut self) -> Result<Value<'a>, FormatError> {
+    self.skip_whitespace();
+    match self.peek() {
+      Some(b'{' | b'[') => self.nested(),
+      Some(b'"') => self.string().map(|_| Value::Other),
+      Some(b'-' | b'0'..=b'9') => Ok(self.number()),
+      _ if self.eat("null") || self.eat("true") || self.eat("false") => Ok(Value::Other),
+      _ => Err(self.expected("a value")),
+    }
+  }
+
+  /// Parses an array or object, so long as it isn't nested too deeply
+  fn nested(&mut self) -> Result<Value<'a>, FormatError> {
+    if self.depth >= MAX_DEPTH {
+      return Err(self.expected("a less deeply nested value"));
+    }
+    self.depth += 1;
+    let value = match self.peek() {
+      Some(b'{') => self.object(),
+      _ => self.array(),
+    };
+    self.depth -= 1;
+    value
+  }
+
+  fn number(&mut self) -> Value<'a> {
+    let start = self.position;
+    while let Some(b'-' | b'+' | b'.' | b'e' | b'E' | b'0'..=b'9') = self.peek() {
+      self.position += 1;
+    }
+    Value::Number(&self.input[start..self.position])
+  }
+
+  fn string(&mut self) -> Result<String, FormatError> {
+    if !self.eat("\"") {
+      return Err(self.expected("a string"));
+    }
+
+    let mut string = String::new();
+    let mut chars = self.input[self.position..].char_indices();
+    while let Some((offset, c)) = chars.next() {
+      match c {
+        '"' => {
+          self.position += offset + 1;
+          return Ok(string);
+        }
+        '\\' => {
+          let escaped = match chars.next().map(|(_, c)| c) {
+            Some('n') => '\n',
+            Some('t') => '\t',
+            Some('r') => '\r',
+            Some('b') => '\u{8}',
+            Some('f') => '\u{c}',
+            Some('u') => {
+              let digits: String = chars.by_ref().take(4).map(|(_, c)| c).collect();
+              u32::from_str_radix(&digits, 16)
+                .ok()
+                .and_then(char::from_u32)
+                .unwrap_or(char::REPLACEMENT_CHARACTER)
+            }
+            Some(c @ ('"' | '\\' | '/')) => c,
+            _ => return Err(self.expected_at(self.position + offset, "an escape sequence")),
+          };
+          string.push(escaped);
+        }
+        c => string.push(c),
+      }
+    }
+    Err(FormatError::UnexpectedEnd)
+  }
+
+  fn array(&mut self) -> Result<Value<'a>, FormatError> {
+    self.eat("[");
+    let mut values = vec![];
+    self.skip_whitespace();
+    if self.eat("]") {
+      return Ok(Value::Array(values));
+    }
+
+    loop {
+      values.push(self.value()?);
+      self.skip_whitespace();
+      if self.eat("]") {
+        return Ok(Value::Array(values));
+      }
+      if !self.eat(",") {
+        return Err(self.expected("`,` or `]`"));
+      }
+    }
+  }
+
+  fn object(&mut self) -> Result<Value<'a>, FormatError> {
+    self.eat("{");
+    let mut fields = vec![];
+    self.skip_whitespace();
+    if self.eat("}") {
+      return Ok(Value::Object(fields));
+    }
+
+    loop {
+      self.skip_whitespace();
+      let key = self.string()?;
+      self.skip_whitespace();
+      if !self.eat(":") {
+        return Err(self.expected("`:`"));
+      }
+      fields.push((key, self.value()?));
+
+      self.skip_whitespace();
+      if self.eat("}") {
+        return Ok(Value::Object(fields));
+      }
+      if !self.eat(",") {
+        return Err(self.expected("`,` or `}`"));
+      }
+    }
+  }
+}
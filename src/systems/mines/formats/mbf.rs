@@ -0,0 +1,86 @@
+//! The Minesweeper Arbiter board format (`.mbf`).
+//!
+//! This is a binary format that only stores the placement of mines:
+//!
+//! | bytes | contents                                   |
+//! |-------|--------------------------------------------|
+//! | 1     | the width of the board                     |
+//! | 1     | the height of the board                    |
+//! | 2     | the number of mines, big endian            |
+//! | 2 × n | the `x` then `y` position of each mine     |
+//!
+//! As no tiles are revealed, reading always produces a game with no revealed tiles.
+//!
+//! ## Examples
+//!
+//! ```
+//! # use farc3::systems::mines::formats::mbf;
+//! let bytes = [3, 2, 0, 1, 2, 0];
+//! let game = mbf::read(&bytes).unwrap();
+//!
+//! assert!(game.layout.is_mine(&(2, 0)));
+//! assert!(game.revealed.is_empty());
+//! assert_eq!(mbf::write(&game).unwrap(), bytes);
+//! ```
+
+use super::{FormatError, Game, check_bounds};
+use crate::systems::mines::generator::Layout;
+
+/// The number of bytes before the mine positions
+const HEADER_LEN: usize = 4;
+
+/// Reads a game from the bytes of an `.mbf` file
+///
+/// ## Errors
+///
+/// - [`FormatError::UnexpectedEnd`] when there are fewer mines than the header states
+/// - [`FormatError::TrailingData`] when there are more mines than the header states
+/// - [`FormatError::OutOfBounds`] when a mine is placed outside of the board
+pub fn read(bytes: &[u8]) -> Result<Game, FormatError> {
+  let Some(&[width, height, count_hi, count_lo]) = bytes.first_chunk::<HEADER_LEN>() else {
+    return Err(FormatError::UnexpectedEnd);
+  };
+  let count = u16::from_be_bytes([count_hi, count_lo]) as usize;
+
+  let end = HEADER_LEN + 2 * count;
+  let positions = bytes
+    .get(HEADER_LEN..end)
+    .ok_or(FormatError::UnexpectedEnd)?;
+  if bytes.len() > end {
+    return Err(FormatError::TrailingData { position: end });
+  }
+
+  let mines: Vec<_> = positions
+    .chunks_exact(2)
+    .map(|pos| (pos[0] as usize, pos[1] as usize))
+    .collect();
+  let layout = Layout::new(width as usize, height as usize, mines.iter().copied());
+  for &tile in &mines {
+    check_bounds(&layout, tile)?;
+  }
+
+  Game::new(layout, [])
+}
+
+/// Writes the mine layout of a game to the bytes of an `.mbf` file.\
+/// Mines are written in row order and the revealed tiles are discarded.
+///
+/// ## Errors
+///
+/// - [`FormatError::TooLarge`] when the board is wider or taller than 255 tiles,\
+///   or has more than 65535 mines
+pub fn write(game: &Game) -> Result<Vec<u8>, FormatError> {
+  let layout = &game.layout;
+  let width = u8::try_from(layout.width()).map_err(|_| FormatError::TooLarge)?;
+  let height = u8::try_from(layout.height()).map_err(|_| FormatError::TooLarge)?;
+  let count = u16::try_from(layout.mines().len()).map_err(|_| FormatError::TooLarge)?;
+
+  let mut bytes = Vec::with_capacity(HEADER_LEN + 2 * count as usize);
+  bytes.extend([width, height]);
+  bytes.extend(count.to_be_bytes());
+  for tile in layout.tiles().filter(|tile| layout.is_mine(tile)) {
+    // both coordinates fit, as they're smaller than the width and height
+    bytes.extend([tile.0 as u8, tile.1 as u8]);
+  }
+  Ok(bytes)
+}
@@ -0,0 +1,178 @@
+//! Reading and writing minesweeper boards in common interchange formats.
+//!
+//! Each format reads into a [`Game`], holding both the true placement of mines\
+//! and which tiles have been revealed, from which a [`System`] can be built.
+//!
+//! Supported formats are:
+//!
+//! - [`text`] for plain text grids
+//! - [`mbf`] for the Minesweeper Arbiter binary layout
+//! - [`json`] for a JSON board schema
+
+use std::collections::HashSet;
+use std::error::Error;
+use std::fmt;
+
+use super::{
+  board::MineBoard,
+  constraint::MineConstraint,
+  errors::MineConflicts,
+  generator::{Layout, Tile},
+  topology::{Square, Topology},
+};
+use crate::system::System;
+
+pub mod json;
+pub mod mbf;
+pub mod text;
+
+/// A minesweeper game, with its mine layout and the tiles revealed so far
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Game {
+  /// The placement of mines on the board
+  pub layout: Layout,
+  /// The tiles that have been revealed, which never contain mines
+  pub revealed: HashSet<Tile>,
+}
+
+impl Game {
+  /// Constructs a game from its layout and revealed tiles
+  ///
+  /// ## Errors
+  ///
+  /// - [`FormatError::OutOfBounds`] when a revealed tile isn't on the board
+  /// - [`FormatError::RevealedMine`] when a revealed tile contains a mine
+  pub fn new(
+    layout: Layout,
+    revealed: impl IntoIterator<Item = Tile>,
+  ) -> Result<Self, FormatError> {
+    let revealed: HashSet<_> = revealed.into_iter().collect();
+    for &tile in &revealed {
+      check_bounds(&layout, tile)?;
+      if layout.is_mine(&tile) {
+        return Err(FormatError::RevealedMine { tile });
+      }
+    }
+    Ok(Self { layout, revealed })
+  }
+
+  /// The clue shown on each revealed tile
+  pub fn clues(&self) -> impl Iterator<Item = (Tile, usize)> + '_ {
+    self
+      .revealed
+      .iter()
+      .map(|&tile| (tile, self.layout.clue(&tile)))
+  }
+
+  /// Constructs the system of constraints given by the revealed clues
+  pub fn system(&self) -> System<MineConstraint<Tile>> {
+    let square = Square::new(self.layout.width(), self.layout.height());
+    square.system(self.clues())
+  }
+
+  /// Constructs a board with every revealed clue already applied
+  pub fn board(&self) -> Result<MineBoard<Square>, MineConflicts<Tile>> {
+    let square = Square::new(self.layout.width(), self.layout.height());
+    let mut board = MineBoard::new(square);
+    for (tile, clue) in self.clues() {
+      board.reveal(tile, clue)?;
+    }
+    Ok(board)
+  }
+}
+
+/// Checks that a tile lies on the board
+fn check_bounds(layout: &Layout, tile: Tile) -> Result<(), FormatError> {
+  match tile.0 < layout.width() && tile.1 < layout.height() {
+    true => Ok(()),
+    false => Err(FormatError::OutOfBounds { tile }),
+  }
+}
+
+/// The error produced when a board can't be read or written
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FormatError {
+  /// The input ended before the whole board was read
+  UnexpectedEnd,
+  /// The input continued after the whole board was read
+  TrailingData {
+    /// The offset of the first unread byte
+    position: usize,
+  },
+  /// A text grid contained a symbol with no meaning
+  InvalidSymbol {
+    /// The line the symbol is on, starting from 0
+    line: usize,
+    /// The column the symbol is in, starting from 0
+    column: usize,
+    /// The symbol found
+    symbol: char,
+  },
+  /// A row of a text grid had a different width to the first row
+  RaggedRow {
+    /// The line of the row, starting from 0
+    line: usize,
+  },
+  /// A revealed clue didn't match the number of mines around it
+  WrongClue {
+    /// The revealed tile
+    tile: Tile,
+    /// The clue shown on the tile
+    shown: usize,
+    /// The number of mines around the tile
+    actual: usize,
+  },
+  /// A tile was placed outside of the board
+  OutOfBounds {
+    /// The tile outside of the board
+    tile: Tile,
+  },
+  /// A tile was both revealed and contained a mine
+  RevealedMine {
+    /// The revealed mine
+    tile: Tile,
+  },
+  /// The board is too large to be read or written in the format
+  TooLarge,
+  /// JSON input was malformed or didn't follow the board schema
+  InvalidJson {
+    /// The offset where the problem was found
+    position: usize,
+    /// What was expected at `position`
+    expected: &'static str,
+  },
+}
+
+impl fmt::Display for FormatError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Self::UnexpectedEnd => write!(f, "unexpected end of input"),
+      Self::TrailingData { position } => write!(f, "unexpected data at offset {position}"),
+      Self::InvalidSymbol {
+        line,
+        column,
+        symbol,
+      } => write!(
+        f,
+        "invalid symbol {symbol:?} at line {line}, column {column}"
+      ),
+      Self::RaggedRow { line } => write!(f, "row at line {line} has the wrong width"),
+      Self::WrongClue {
+        tile: (x, y),
+        shown,
+        actual,
+      } => write!(
+        f,
+        "tile ({x}, {y}) shows {shown} but has {actual} mines around it"
+      ),
+      Self::OutOfBounds { tile: (x, y) } => write!(f, "tile ({x}, {y}) is outside the board"),
+      Self::RevealedMine { tile: (x, y) } => write!(f, "tile ({x}, {y}) is a revealed mine"),
+      Self::TooLarge => write!(f, "board is too large for the format"),
+      Self::InvalidJson { position, expected } => {
+        write!(f, "expected {expected} at offset {position}")
+      }
+    }
+  }
+}
+
+impl Error for FormatError {}
@@ -0,0 +1,107 @@
+//! Plain text grids of mines, where each line is a row of the board.
+//!
+//! Each tile is written as one of:
+//!
+//! - `.` for a hidden safe tile
+//! - `*` for a hidden mine
+//! - `0` to `8` for a revealed tile, showing the number of mines around it
+//!
+//! ## Examples
+//!
+//! ```
+//! # use farc3::systems::mines::formats::text;
+//! let game = text::read("01*\n01.\n").unwrap();
+//! assert_eq!(game.layout.mines().len(), 1);
+//! assert_eq!(game.revealed.len(), 4);
+//! assert_eq!(text::write(&game), "01*\n01.\n");
+//! ```
+
+use super::{FormatError, Game};
+use crate::systems::mines::generator::{Layout, Tile};
+
+/// The symbol for a hidden safe tile
+pub const HIDDEN: char = '.';
+/// The symbol for a hidden mine
+pub const MINE: char = '*';
+
+/// Reads a game from a text grid.\
+/// Trailing blank lines and `\r` line endings are ignored.
+///
+/// ## Errors
+///
+/// - [`FormatError::UnexpectedEnd`] when there are no rows
+/// - [`FormatError::InvalidSymbol`] for an unknown symbol
+/// - [`FormatError::RaggedRow`] when rows have different widths
+/// - [`FormatError::WrongClue`] when a revealed clue doesn't match the mines around it
+pub fn read(input: &str) -> Result<Game, FormatError> {
+  let rows: Vec<&str> = input
+    .trim_end_matches(['\r', '\n'])
+    .lines()
+    .map(|line| line.trim_end_matches('\r'))
+    .collect();
+  let Some(width) = rows.first().map(|row| row.chars().count()) else {
+    return Err(FormatError::UnexpectedEnd);
+  };
+
+  let mut mines = vec![];
+  let mut clues: Vec<(Tile, usize)> = vec![];
+  for (y, row) in rows.iter().enumerate() {
+    if row.chars().count() != width {
+      return Err(FormatError::RaggedRow { line: y });
+    }
+
+    for (x, symbol) in row.chars().enumerate() {
+      match symbol {
+        HIDDEN => {}
+        MINE => mines.push((x, y)),
+        _ => {
+          let clue =
+            symbol
+              .to_digit(10)
+              .filter(|&clue| clue <= 8)
+              .ok_or(FormatError::InvalidSymbol {
+                line: y,
+                column: x,
+                symbol,
+              })?;
+          clues.push(((x, y), clue as usize));
+        }
+      }
+    }
+  }
+
+  let layout = Layout::new(width, rows.len(), mines);
+  for &(tile, shown) in &clues {
+    let actual = layout.clue(&tile);
+    if shown != actual {
+      return Err(FormatError::WrongClue {
+        tile,
+        shown,
+        actual,
+      });
+    }
+  }
+  Game::new(layout, clues.into_iter().map(|(tile, _)| tile))
+}
+
+/// Writes a game as a text grid, with a trailing newline after each row
+pub fn write(game: &Game) -> String {
+  let layout = &game.layout;
+  let mut output = String::with_capacity((layout.width() + 1) * layout.height());
+
+  for y in 0..layout.height() {
+    for x in 0..layout.width() {
+      let tile = (x, y);
+      let symbol = if layout.is_mine(&tile) {
+        MINE
+      } else if game.revealed.contains(&tile) {
+        char::from_digit(layout.clue(&tile) as u32, 10).expect("clues are at most 8")
+      } else {
+        HIDDEN
+      };
+      output.push(symbol);
+    }
+    output.push('\n');
+  }
+  output
+}
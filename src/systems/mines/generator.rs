@@ -0,0 +1,244 @@
+//! Generation of minesweeper boards that can be solved without guessing
+
+use std::collections::HashSet;
+
+use super::{
+  constraint::MineConstraint,
+  rating::{Rating, rate},
+  topology::{Square, Topology},
+};
+use crate::system::System;
+
+/// The position of a tile on a board, as `(x, y)`
+pub type Tile = (usize, usize);
+
+/// The number of layouts tried before giving up on generating a board
+pub const MAX_ATTEMPTS: usize = 1000;
+
+/// The placement of mines on a rectangular board,\
+/// where each tile neighbours the 8 tiles around it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Layout {
+  width: usize,
+  height: usize,
+  mines: HashSet<Tile>,
+}
+
+impl Layout {
+  /// Constructs a mine layout
+  ///
+  /// ## Arguments
+  ///
+  /// - `width`: the number of columns on the board
+  /// - `height`: the number of rows on the board
+  /// - `mines`: the tiles that contain mines
+  pub fn new(width: usize, height: usize, mines: impl IntoIterator<Item = Tile>) -> Self {
+    Self {
+      width,
+      height,
+      mines: mines.into_iter().collect(),
+    }
+  }
+
+  /// The number of columns on the board
+  pub fn width(&self) -> usize {
+    self.width
+  }
+
+  /// The number of rows on the board
+  pub fn height(&self) -> usize {
+    self.height
+  }
+
+  /// The tiles that contain mines
+  pub fn mines(&self) -> &HashSet<Tile> {
+    &self.mines
+  }
+
+  /// Whether the given tile contains a mine
+  pub fn is_mine(&self, tile: &Tile) -> bool {
+    self.mines.contains(tile)
+  }
+
+  /// All tiles on the board, in row order
+  pub fn tiles(&self) -> impl Iterator<Item = Tile> + '_ {
+    (0..self.height).flat_map(|y| (0..self.width).map(move |x| (x, y)))
+  }
+
+  /// The tiles on the board around a given tile
+  pub fn neighbours(&self, tile: &Tile) -> Vec<Tile> {
+    Square::new(self.width, self.height).neighbours(tile)
+  }
+
+  /// The number of mines around a given tile
+  pub fn clue(&self, tile: &Tile) -> usize {
+    self
+      .neighbours(tile)
+      .iter()
+      .filter(|other| self.is_mine(other))
+      .count()
+  }
+
+  /// Whether the board can be solved by pure logic, starting from the given tile.
+  ///
+  /// This simulates play, where each revealed clue is added to a [`System`]\
+  /// and all tiles popped from it are either revealed or flagged.\
+  /// The board is solvable when every safe tile gets revealed.
+  pub fn solvable_from(&self, start: Tile) -> bool {
+    if self.is_mine(&start) {
+      return false;
+    }
+
+    let mut system = System::default();
+    let mut revealed = HashSet::new();
+    let mut flagged = HashSet::new();
+    let mut queue = vec![start];
+
+    while !queue.is_empty() {
+      while let Some(tile) = queue.pop() {
+        if !revealed.insert(tile) {
+          continue;
+        }
+
+        let neighbours = self.neighbours(&tile);
+        let known_mines = neighbours
+          .iter()
+          .filter(|other| flagged.contains(*other))
+          .count();
+        let unknown = neighbours
+          .into_iter()
+          .filter(|other| !revealed.contains(other) && !flagged.contains(other));
+
+        system.insert(MineConstraint::new([tile], 0));
+        system.insert(MineConstraint::new(unknown, self.clue(&tile) - known_mines));
+      }
+
+      let Ok(sltn) = system.pop_solution() else {
+        return false;
+      };
+      for (tile, mine) in sltn {
+        if mine {
+          flagged.insert(tile);
+        } else if !revealed.contains(&tile) {
+          queue.push(tile);
+        }
+      }
+    }
+
+    revealed.len() + self.mines.len() == self.width * self.height
+  }
+
+  /// Rates how hard the board is to solve, starting from the given tile
+  ///
+  /// ## See also
+  ///
+  /// [`rate`] for rating boards of any shape
+  pub fn rate(&self, start: Tile) -> Rating<Tile> {
+    rate(&Square::new(self.width, self.height), &self.mines, start)
+  }
+}
+
+/// A generator for minesweeper boards that can be solved without guessing.
+///
+/// Mines are placed at random, avoiding the first tile revealed and its neighbours.\
+/// Each layout is then checked by simulating play and rejected when a guess is needed.
+///
+/// ## Examples
+///
+/// ```
+/// # use farc3::systems::mines::generator::Generator;
+/// let mut generator = Generator::new(9, 9, 0.12, 42);
+/// let layout = generator.generate((4, 4)).unwrap();
+///
+/// assert_eq!(layout.mines().len(), 10);
+/// assert!(layout.solvable_from((4, 4)));
+/// ```
+#[derive(Debug, Clone)]
+pub struct Generator {
+  width: usize,
+  height: usize,
+  mines: usize,
+  rng: SplitMix64,
+}
+
+impl Generator {
+  /// Constructs a board generator
+  ///
+  /// ## Arguments
+  ///
+  /// - `width`: the number of columns on generated boards
+  /// - `height`: the number of rows on generated boards
+  /// - `density`: the fraction of tiles that should contain mines
+  /// - `seed`: the seed for random mine placement
+  pub fn new(width: usize, height: usize, density: f64, seed: u64) -> Self {
+    let area = width * height;
+    let mines = (density.clamp(0.0, 1.0) * area as f64).round() as usize;
+
+    Self {
+      width,
+      height,
+      mines: mines.min(area.saturating_sub(1)),
+      rng: SplitMix64(seed),
+    }
+  }
+
+  /// Generates a layout that can be solved without guessing
+  ///
+  /// ## Arguments
+  ///
+  /// - `start`: the first tile that will be revealed, which is never a mine
+  ///
+  /// ## Returns
+  ///
+  /// A solvable layout, or `None` if none was found within [`MAX_ATTEMPTS`] tries
+  pub fn generate(&mut self, start: Tile) -> Option<Layout> {
+    (0..MAX_ATTEMPTS)
+      .map(|_| self.place(start))
+      .find(|layout| layout.solvable_from(start))
+  }
+
+  /// Places mines at random, avoiding `start` and, where possible, its neighbours
+  fn place(&mut self, start: Tile) -> Layout {
+    let board = Layout::new(self.width, self.height, []);
+    let mut opening: HashSet<Tile> = board.neighbours(&start).into_iter().collect();
+    opening.insert(start);
+
+    let mut candidates: Vec<Tile> = board
+      .tiles()
+      .filter(|tile| !opening.contains(tile))
+      .collect();
+    if candidates.len() < self.mines {
+      candidates = board.tiles().filter(|&tile| tile != start).collect();
+    }
+
+    // partial Fisher-Yates shuffle to pick the mine tiles
+    for idx in 0..self.mines {
+      let swap = idx + self.rng.below(candidates.len() - idx);
+      candidates.swap(idx, swap);
+    }
+    candidates.truncate(self.mines);
+
+    Layout::new(self.width, self.height, candidates)
+  }
+}
+
+/// A small, seedable pseudo-random number generator.
+///
+/// Algorithm from [Steele, Lea and Flood](https://doi.org/10.1145/2714064.2660195)
+#[derive(Debug, Clone)]
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+  fn next_u64(&mut self) -> u64 {
+    self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    let mut z = self.0;
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+  }
+
+  /// A random number in `0..n`
+  fn below(&mut self, n: usize) -> usize {
+    (self.next_u64() % n as u64) as usize
+  }
+}
@@ -0,0 +1,298 @@
+//! Recommendations for which tile to reveal when no tile is known to be safe
+
+use std::collections::{HashMap, HashSet};
+use std::fmt::Debug;
+use std::hash::Hash;
+
+use super::{constraint::MineConstraint, utils::ln_choose};
+use crate::{constraint::Constraint, system::System};
+
+/// The precision that mine probabilities are compared at when ranking guesses.\
+/// Probabilities closer together than this are treated as a tie.
+const PRECISION: f64 = 1e9;
+
+/// A tile that could be revealed, along with how risky and how useful revealing it is.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Guess<V> {
+  /// The tile to reveal
+  pub tile: V,
+  /// The probability that `tile` is a mine
+  pub mine_chance: f64,
+  /// The probability that, given `tile` is safe,\
+  /// the clue it reveals lets another tile be decided
+  pub progress_chance: f64,
+}
+
+/// One way that mines could be placed on the frontier of a board
+struct Scenario<V> {
+  /// The frontier tiles that contain mines
+  mines: HashSet<V>,
+  /// The number of mines left to place outside of the frontier
+  remaining: usize,
+}
+
+/// Ranks unknown tiles by how likely they are to contain a mine.
+///
+/// Unknown tiles are split into:
+/// 1. frontier tiles, that are affected by some constraint in the system
+/// 2. all other tiles, that are only affected by the total number of mines
+///
+/// Each solution to the frontier is weighted by the number of ways\
+/// that the remaining mines can be placed on all other tiles.
+///
+/// ## Examples
+///
+/// ```
+/// # use farc3::prelude::*;
+/// # use farc3::systems::mines::guess::GuessAdvisor;
+/// // 1 mine among tiles 0 and 1, with 1 more mine among tiles 2, 3, 4 and 5
+/// let sys = System::from([MineConstraint::new([0, 1], 1)]);
+/// let advisor = GuessAdvisor::new(sys, 0..6, 2, |&tile: &i32| [tile - 1, tile + 1]);
+///
+/// let guess = advisor.best().unwrap();
+/// assert!(guess.tile >= 2);
+/// assert_eq!(guess.mine_chance, 0.25);
+/// ```
+pub struct GuessAdvisor<V: Hash + Eq + Clone + Debug, N> {
+  /// The constraints on the frontier of the board
+  system: System<MineConstraint<V>>,
+  /// Unknown tiles affected by some constraint in `system`
+  frontier: HashSet<V>,
+  /// Unknown tiles not affected by any constraint in `system`
+  outside: HashSet<V>,
+  /// The total number of mines left among all unknown tiles
+  mines: usize,
+  /// The tiles that a revealed tile would give a clue for
+  neighbours: N,
+}
+
+impl<V, N, I> GuessAdvisor<V, N>
+where
+  V: Hash + Eq + Clone + Debug,
+  N: Fn(&V) -> I,
+  I: IntoIterator<Item = V>,
+{
+  /// Constructs a guess advisor for a partially revealed board
+  ///
+  /// ## Arguments
+  ///
+  /// - `system`: the constraints given by revealed clues
+  /// - `unknown`: all tiles that are neither revealed nor flagged
+  /// - `mines`: the number of mines left among `unknown` tiles
+  /// - `neighbours`: the tiles that a clue for a given tile would count mines in
+  ///
+  /// ## Returns
+  ///
+  /// A new [`GuessAdvisor`]
+  pub fn new(
+    system: System<MineConstraint<V>>,
+    unknown: impl IntoIterator<Item = V>,
+    mines: usize,
+    neighbours: N,
+  ) -> Self {
+    let frontier: HashSet<V> = (&system)
+      .into_iter()
+      .flat_map(|cons| cons.variables())
+      .collect();
+    let outside = unknown
+      .into_iter()
+      .filter(|tile| !frontier.contains(tile))
+      .collect();
+
+    Self {
+      system,
+      frontier,
+      outside,
+      mines,
+      neighbours,
+    }
+  }
+
+  /// The probability that each unknown tile contains a mine.
+  ///
+  /// ## Returns
+  ///
+  /// A map from tiles to mine probabilities,\
+  /// which is empty when no placement of mines is consistent.
+  pub fn probabilities(&self) -> HashMap<V, f64> {
+    self.probabilities_in(&self.scenarios())
+  }
+
+  /// Ranks all unknown tiles, from the best tile to reveal to the worst.
+  ///
+  /// Tiles are ranked by:
+  /// 1. the minimum probability of containing a mine
+  /// 2. the maximum probability of leading to new deductions
+  pub fn rank(&self) -> Vec<Guess<V>> {
+    let scenarios = self.scenarios();
+    let known: HashSet<V> = self
+      .system
+      .clone()
+      .pop_solution()
+      .map(|sltn| sltn.into_iter().map(|(tile, _)| tile).collect())
+      .unwrap_or_default();
+
+    let mut guesses: Vec<_> = self
+      .probabilities_in(&scenarios)
+      .into_iter()
+      .map(|(tile, mine_chance)| Guess {
+        progress_chance: self.progress_chance(&tile, &scenarios, &known),
+        tile,
+        mine_chance,
+      })
+      .collect();
+
+    guesses.sort_by(|guess0, guess1| {
+      let chance0 = (guess0.mine_chance * PRECISION).round();
+      let chance1 = (guess1.mine_chance * PRECISION).round();
+      chance0
+        .total_cmp(&chance1)
+        .then(guess1.progress_chance.total_cmp(&guess0.progress_chance))
+    });
+    guesses
+  }
+
+  /// The best tile to reveal, as ranked by [`GuessAdvisor::rank`]
+  pub fn best(&self) -> Option<Guess<V>> {
+    self.rank().into_iter().next()
+  }
+
+  /// All placements of mines on the frontier that respect the total mine count
+  fn scenarios(&self) -> Vec<Scenario<V>> {
+    self
+      .system
+      .clone()
+      .solve()
+      .filter_map(|sltn| {
+        let mines: HashSet<V> = sltn
+          .into_iter()
+          .filter_map(|(tile, mine)| mine.then_some(tile))
+          .collect();
+        let remaining = self.mines.checked_sub(mines.len())?;
+        (remaining <= self.outside.len()).then_some(Scenario { mines, remaining })
+      })
+      .collect()
+  }
+
+  /// Calculates mine probabilities, given all possible frontier scenarios
+  fn probabilities_in(&self, scenarios: &[Scenario<V>]) -> HashMap<V, f64> {
+    let weights = normalise(
+      scenarios
+        .iter()
+        .map(|scenario| ln_choose(self.outside.len(), scenario.remaining)),
+    );
+    let total: f64 = weights.iter().sum();
+    if total == 0.0 {
+      return HashMap::new();
+    }
+
+    let mut probs: HashMap<V, f64> = self
+      .frontier
+      .iter()
+      .map(|tile| (tile.clone(), 0.0))
+      .collect();
+    let mut outside = 0.0;
+    for (scenario, weight) in scenarios.iter().zip(weights) {
+      for tile in &scenario.mines {
+        *probs
+          .get_mut(tile)
+          .expect("mines are placed on frontier tiles") += weight;
+      }
+      if !self.outside.is_empty() {
+        outside += weight * (scenario.remaining as f64) / (self.outside.len() as f64);
+      }
+    }
+    probs.extend(self.outside.iter().map(|tile| (tile.clone(), outside)));
+
+    for prob in probs.values_mut() {
+      *prob /= total;
+    }
+    probs
+  }
+
+  /// The probability that revealing `tile` lets another tile be decided,\
+  /// given that `tile` is safe.
+  ///
+  /// ## Arguments
+  ///
+  /// - `tile`: the tile to reveal
+  /// - `scenarios`: all placements of mines on the frontier
+  /// - `known`: the tiles that can already be decided without revealing `tile`
+  fn progress_chance(&self, tile: &V, scenarios: &[Scenario<V>], known: &HashSet<V>) -> f64 {
+    let neighbours: Vec<V> = (self.neighbours)(tile)
+      .into_iter()
+      .filter(|other| other != tile)
+      .filter(|other| self.frontier.contains(other) || self.outside.contains(other))
+      .collect();
+
+    // the non-frontier tiles that mines could be placed in, if `tile` is safe
+    let pool = self.outside.len() - usize::from(self.outside.contains(tile));
+    let outer = neighbours
+      .iter()
+      .filter(|other| self.outside.contains(*other))
+      .count();
+
+    // the weight of each clue value that `tile` could reveal
+    let mut outcomes = vec![];
+    for scenario in scenarios {
+      if scenario.mines.contains(tile) || pool < scenario.remaining {
+        continue;
+      }
+
+      let inner = neighbours
+        .iter()
+        .filter(|other| scenario.mines.contains(*other))
+        .count();
+      for count in 0..=outer.min(scenario.remaining) {
+        let rest = scenario.remaining - count;
+        if pool - outer < rest {
+          continue;
+        }
+        let weight = ln_choose(outer, count) + ln_choose(pool - outer, rest);
+        outcomes.push((inner + count, weight));
+      }
+    }
+
+    let weights = normalise(outcomes.iter().map(|&(_, weight)| weight));
+    let mut clues: HashMap<usize, f64> = HashMap::new();
+    for (&(clue, _), weight) in outcomes.iter().zip(weights) {
+      *clues.entry(clue).or_default() += weight;
+    }
+
+    let total: f64 = clues.values().sum();
+    if total == 0.0 {
+      return 0.0;
+    }
+
+    let progress: f64 = clues
+      .into_iter()
+      .filter(|&(clue, _)| self.deduces(tile, &neighbours, clue, known))
+      .map(|(_, weight)| weight)
+      .sum();
+    progress / total
+  }
+
+  /// Whether revealing the given clue for `tile` lets another tile be decided
+  fn deduces(&self, tile: &V, neighbours: &[V], clue: usize, known: &HashSet<V>) -> bool {
+    let mut sys = self.system.clone();
+    sys.insert(MineConstraint::new([tile.clone()], 0));
+    sys.insert(MineConstraint::new(neighbours.iter().cloned(), clue));
+
+    let Ok(sltn) = sys.pop_solution() else {
+      return false;
+    };
+    sltn
+      .into_iter()
+      .any(|(other, _)| &other != tile && !known.contains(&other))
+  }
+}
+
+/// Converts log weights into weights, scaled so that the largest weight is `1`
+fn normalise(log_weights: impl Iterator<Item = f64>) -> Vec<f64> {
+  let log_weights: Vec<f64> = log_weights.collect();
+  let max = log_weights
+    .iter()
+    .copied()
+    .fold(f64::NEG_INFINITY, f64::max);
+  log_weights.into_iter().map(|lw| (lw - max).exp()).collect()
+}
@@ -1,7 +1,15 @@
 //! Constraint Satisfaction Problems for minesweeper games.
 pub mod assignment;
+pub mod board;
 pub mod constraint;
+pub mod elimination;
+pub mod endgame;
 pub mod errors;
+pub mod formats;
+pub mod generator;
+pub mod guess;
+pub mod rating;
+pub mod topology;
 pub mod utils;
 
 pub mod prelude {
@@ -0,0 +1,235 @@
+//! Difficulty ratings of minesweeper boards, by the logic needed to solve them
+
+use std::collections::HashSet;
+use std::fmt::Debug;
+use std::hash::Hash;
+
+use super::{assignment::MineAssignment, constraint::MineConstraint, topology::Topology};
+use crate::{assignment::Assignment, constraint::Constraint, system::System};
+
+/// The techniques used to decide tiles, from the easiest to the hardest
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Technique {
+  /// A single clue decides all of its tiles on its own
+  Trivial,
+  /// Clues decide tiles after being reduced by clues that are subsets of them,\
+  /// as done by [`MineConstraint::reduce`]
+  Subset,
+  /// A clue decides tiles when combined with all clues that overlap it
+  Overlap,
+  /// Clues decide tiles when combined with the number of mines left on the board
+  GlobalCount,
+  /// Tiles are only decided by trying every placement of mines on the board
+  Search,
+}
+
+impl Technique {
+  /// How much using this technique adds to the difficulty of a board
+  pub fn weight(&self) -> usize {
+    match self {
+      Self::Trivial => 1,
+      Self::Subset => 2,
+      Self::Overlap => 4,
+      Self::GlobalCount => 8,
+      Self::Search => 16,
+    }
+  }
+}
+
+/// A single deduction made whilst solving a board
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Step<V: Hash + Eq> {
+  /// The easiest technique that could decide any tiles
+  pub technique: Technique,
+  /// The tiles decided by `technique`
+  pub deduced: MineAssignment<V>,
+}
+
+/// The difficulty of a board, along with each step taken to solve it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rating<V: Hash + Eq> {
+  /// The deductions made, in the order they were made
+  pub steps: Vec<Step<V>>,
+  /// Whether every safe tile was revealed without needing a guess
+  pub solved: bool,
+}
+
+impl<V: Hash + Eq> Rating<V> {
+  /// The total difficulty of all steps taken to solve the board
+  pub fn score(&self) -> usize {
+    self.steps.iter().map(|step| step.technique.weight()).sum()
+  }
+
+  /// The hardest technique needed to solve the board
+  pub fn hardest(&self) -> Option<Technique> {
+    self.steps.iter().map(|step| step.technique).max()
+  }
+}
+
+/// Finds tiles that can be decided, using the easiest technique possible
+///
+/// ## Arguments
+///
+/// - `clues`: the constraints given by revealed clues, only over unknown tiles
+/// - `unknown`: all tiles that are neither revealed nor flagged
+/// - `mines`: the number of mines left among `unknown` tiles
+///
+/// ## Returns
+///
+/// The deduction made, or `None` if a guess is needed
+pub fn deduce<V: Hash + Eq + Clone + Debug>(
+  clues: &[MineConstraint<V>],
+  unknown: &HashSet<V>,
+  mines: usize,
+) -> Option<Step<V>> {
+  let global = MineConstraint::new(unknown.iter().cloned(), mines);
+  let step = |technique, deduced: MineAssignment<V>| {
+    (deduced != MineAssignment::default()).then_some(Step { technique, deduced })
+  };
+
+  let trivial = clues
+    .iter()
+    .filter(|clue| clue.size() == 1)
+    .filter_map(|clue| clue.clone().pop_solution())
+    .fold(MineAssignment::default(), Assignment::union);
+
+  step(Technique::Trivial, trivial)
+    .or_else(|| step(Technique::Subset, forced(clues.iter().cloned())))
+    .or_else(|| {
+      let overlaps = clues.iter().map(|clue| {
+        let tiles: HashSet<V> = clue.variables().collect();
+        let local = clues
+          .iter()
+          .filter(|other| other.variables().any(|tile| tiles.contains(&tile)));
+        searched(local.cloned())
+      });
+      step(
+        Technique::Overlap,
+        overlaps.fold(MineAssignment::default(), Assignment::union),
+      )
+    })
+    .or_else(|| {
+      let clues = clues.iter().cloned().chain([global.clone()]);
+      step(Technique::GlobalCount, forced(clues))
+    })
+    .or_else(|| {
+      let clues = clues.iter().cloned().chain([global]);
+      step(Technique::Search, searched(clues))
+    })
+}
+
+/// The tiles decided by reducing constraints against each other
+fn forced<V: Hash + Eq + Clone + Debug>(
+  constraints: impl IntoIterator<Item = MineConstraint<V>>,
+) -> MineAssignment<V> {
+  System::from_iter(constraints)
+    .pop_solution()
+    .unwrap_or_default()
+}
+
+/// The tiles assigned the same value in every solution to the constraints
+fn searched<V: Hash + Eq + Clone + Debug>(
+  constraints: impl IntoIterator<Item = MineConstraint<V>>,
+) -> MineAssignment<V> {
+  System::from_iter(constraints)
+    .solve()
+    .reduce(Assignment::intersection)
+    .unwrap_or_default()
+}
+
+/// Rates how hard a board is to solve, by simulating play from a given tile.
+///
+/// At each step, the easiest technique that decides any tile is used.\
+/// Safe tiles are then revealed and mines flagged, until every safe tile is revealed.
+///
+/// ## Arguments
+///
+/// - `board`: the shape of the board, deciding which tiles each clue counts
+/// - `mines`: the tiles that contain mines
+/// - `start`: the first tile revealed
+///
+/// ## Examples
+///
+/// ```
+/// # use std::collections::HashSet;
+/// # use farc3::systems::mines::rating::{rate, Technique};
+/// # use farc3::systems::mines::topology::Hex;
+/// let board = Hex::new(3, 1);
+/// let rating = rate(&board, &HashSet::from([(1, 0)]), (0, 0));
+///
+/// // tile 1 is a mine by the first clue, tile 2 is safe by the mine count
+/// assert!(rating.solved);
+/// assert_eq!(rating.hardest(), Some(Technique::GlobalCount));
+/// assert_eq!(rating.score(), 9);
+/// ```
+///
+/// ## See also
+///
+/// [`Layout::rate`](super::generator::Layout::rate) for rating square boards
+pub fn rate<T: Topology>(board: &T, mines: &HashSet<T::Tile>, start: T::Tile) -> Rating<T::Tile> {
+  let mut steps = vec![];
+  let mut revealed = HashSet::new();
+  let mut flagged = HashSet::new();
+  let mut queue = vec![start];
+
+  loop {
+    for tile in queue.drain(..) {
+      if mines.contains(&tile) {
+        return Rating {
+          steps,
+          solved: false,
+        };
+      }
+      revealed.insert(tile);
+    }
+
+    let unknown: HashSet<T::Tile> = board
+      .tiles()
+      .filter(|tile| !revealed.contains(tile) && !flagged.contains(tile))
+      .collect();
+    let left = mines.len() - flagged.len();
+    if unknown.len() == left {
+      return Rating {
+        steps,
+        solved: true,
+      };
+    }
+
+    let clues: HashSet<_> = revealed
+      .iter()
+      .map(|tile| {
+        let neighbours = board.neighbours(tile);
+        let clue = neighbours
+          .iter()
+          .filter(|other| mines.contains(*other))
+          .count();
+        let known_mines = neighbours
+          .iter()
+          .filter(|other| flagged.contains(*other))
+          .count();
+        let tiles = neighbours
+          .into_iter()
+          .filter(|other| unknown.contains(other));
+        MineConstraint::new(tiles, clue - known_mines)
+      })
+      .filter(|clue| clue.variables().next().is_some())
+      .collect();
+    let clues: Vec<_> = clues.into_iter().collect();
+
+    let Some(step) = deduce(&clues, &unknown, left) else {
+      return Rating {
+        steps,
+        solved: false,
+      };
+    };
+
+    for (tile, mine) in step.deduced.clone() {
+      if mine {
+        flagged.insert(tile);
+      } else {
+        queue.push(tile);
+      }
+    }
+    steps.push(step);
+  }
+}
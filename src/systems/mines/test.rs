@@ -4,7 +4,7 @@
 mod assignments {
   use super::super::assignment::MineAssignment;
   use crate::prelude::Assignment;
-  use std::collections::HashMap;
+  use std::collections::{HashMap, HashSet};
 
   #[test]
   fn construction() {
@@ -53,6 +53,54 @@ mod assignments {
       ])
     );
   }
+
+  #[test]
+  fn checked_union() {
+    let assign0 = MineAssignment::from_iter([(0, true), (1, false)]);
+    let assign1 = MineAssignment::from_iter([/*      */ (1, false), (3, true)]);
+    let assign = assign0.checked_union(assign1).unwrap();
+    assert_eq!(
+      HashMap::from_iter(assign),
+      HashMap::from([(0, true), (1, false), (3, true)])
+    );
+
+    let assign0 = MineAssignment::from_iter([(0, true), (1, false), (2, true)]);
+    let assign1 = MineAssignment::from_iter([(0, false), (1, false), (2, false)]);
+    let conflicts: HashSet<_> = assign0
+      .checked_union(assign1)
+      .unwrap_err()
+      .into_iter()
+      .collect();
+    assert_eq!(conflicts, HashSet::from([(0, true), (2, true)]));
+  }
+  #[test]
+  fn lookup() {
+    let mut assign = MineAssignment::from_iter([(0, true), (1, false)]);
+    assert_eq!(assign.get(&0), Some(&true));
+    assert_eq!(assign.get(&1), Some(&false));
+    assert_eq!(assign.get(&2), None);
+    assert!(assign.contains(&1));
+    assert_eq!(assign.len(), 2);
+    assert!(!assign.is_empty());
+    assert_eq!(
+      assign
+        .iter()
+        .map(|(&var, &value)| (var, value))
+        .collect::<HashMap<_, _>>(),
+      HashMap::from([(0, true), (1, false)])
+    );
+
+    assert_eq!(assign.insert(0, false), Some(true));
+    assert_eq!(assign.insert(2, true), None);
+    assert_eq!(assign.get(&0), Some(&false));
+    assert_eq!(assign.len(), 3);
+
+    let assign = assign.restrict_to([0, 2, 3]);
+    assert_eq!(
+      HashMap::from_iter(assign),
+      HashMap::from([(0, false), (2, true)])
+    );
+  }
 }
 
 /// Unit testing generic [`Constraints`]
@@ -60,6 +108,7 @@ mod assignments {
 /// [`Constraints`]: crate::systems::generic::assignment::GenericConstraint
 mod constraints {
   use super::super::constraint::MineConstraint;
+  use super::super::errors::{MineConflicts, ReductionCase};
   use crate::prelude::Constraint;
   use std::collections::{HashMap, HashSet};
 
@@ -101,6 +150,32 @@ mod constraints {
       let vars0 = decomp.variables().collect();
       assert!(vars.is_superset(&vars0));
     }
+
+    // decompositions partition the solutions on a single tile
+    let decomps: Vec<_> = cons.decompositions().collect();
+    assert_eq!(decomps.len(), 2);
+    assert_eq!(decomps[0].tiles(), decomps[1].tiles());
+    assert_eq!(decomps[0].tiles().len(), 1);
+
+    let decomps: Vec<_> = MineConstraint::new([0, 1], 0).decompositions().collect();
+    assert_eq!(decomps.len(), 1);
+  }
+
+  #[test]
+  fn split_on() {
+    let cons = MineConstraint::new([0, 1, 2], 2);
+    assert_eq!(
+      cons.split_on(&1),
+      Some(vec![
+        MineConstraint::new([1], 1),
+        MineConstraint::new([1], 0)
+      ])
+    );
+    assert_eq!(
+      MineConstraint::new([0, 1], 2).split_on(&1),
+      Some(vec![MineConstraint::new([1], 1)])
+    );
+    assert_eq!(cons.split_on(&3), None);
   }
 
   #[test]
@@ -150,18 +225,111 @@ mod constraints {
     assert_eq!(cons0.size(), 1);
     assert_eq!(HashSet::from_iter(cons0.variables()), HashSet::new());
   }
+
+  /// Conflicts record the constraints involved and the reduction that failed
+  #[test]
+  fn reduce_conflicts() {
+    let cases = [
+      // 2 mines can't fit in the 1 tile left after removing safe tiles
+      ((vec![0, 1, 2], 2), (vec![0, 1], 0), ReductionCase::AllSafe),
+      // removing 2 mines would leave -1 mines
+      (
+        (vec![0, 1, 2], 1),
+        (vec![1, 2, 3], 3),
+        ReductionCase::AllMine,
+      ),
+      // the subset has more mines than the superset
+      ((vec![0, 1, 2], 1), (vec![0, 1], 2), ReductionCase::AllMine),
+      (
+        (vec![0, 1, 2, 3], 1),
+        (vec![0, 1, 2], 2),
+        ReductionCase::Subset,
+      ),
+    ];
+
+    for ((tiles0, count0), (tiles1, count1), case) in cases {
+      let mut cons0 = MineConstraint::new(tiles0, count0);
+      let cons1 = MineConstraint::new(tiles1, count1);
+
+      let err = cons0.reduce(&cons1).unwrap_err();
+      assert_eq!(
+        err,
+        MineConflicts::Reduction {
+          constraint: cons0.clone(),
+          other: cons1.clone(),
+          case,
+        }
+      );
+      assert_eq!(
+        err.overlap(),
+        cons0.tiles().intersection(cons1.tiles()).collect()
+      );
+    }
+  }
+
+  #[test]
+  fn conflict_display() {
+    let mut cons0 = MineConstraint::new([0], 1);
+    let cons1 = MineConstraint::new([0], 0);
+
+    let err = cons0.reduce(&cons1).unwrap_err();
+    assert_eq!(
+      err.to_string(),
+      "cannot reduce 1 mines among {0} by 0 mines among {0} (all safe), as they overlap on {0}"
+    );
+
+    let err = MineConflicts::Elimination {
+      constraints: vec![cons0, cons1],
+    };
+    assert_eq!(
+      err.to_string(),
+      "no placement of mines satisfies all of 1 mines among {0}, 0 mines among {0}"
+    );
+  }
+
+  #[test]
+  fn satisfied() {
+    let cons = MineConstraint::new([0, 1, 2], 1);
+    assert!(cons.is_satisfied(&[(0, false), (1, true), (2, false), (3, true)].into()));
+    assert!(!cons.is_satisfied(&[(0, true), (1, true), (2, false)].into()));
+    assert!(!cons.is_satisfied(&[(0, false), (1, false), (2, false)].into()));
+    // tile 2 is unassigned
+    assert!(!cons.is_satisfied(&[(0, false), (1, true)].into()));
+  }
+
+  #[test]
+  fn subsumes() {
+    let safe = MineConstraint::new([0, 1, 2], 0);
+    assert!(safe.subsumes(&MineConstraint::new([0, 2], 0)));
+    assert!(!safe.subsumes(&MineConstraint::new([0, 3], 0)));
+    assert!(!safe.subsumes(&MineConstraint::new([0, 2], 1)));
+
+    let mines = MineConstraint::new([0, 1, 2], 3);
+    assert!(mines.subsumes(&MineConstraint::new([1, 2], 2)));
+    assert!(!mines.subsumes(&MineConstraint::new([1, 2], 1)));
+
+    let clue = MineConstraint::new([0, 1, 2], 1);
+    assert!(clue.subsumes(&clue.clone()));
+    assert!(!clue.subsumes(&MineConstraint::new([0, 1], 1)));
+    assert!(!MineConstraint::new([0, 1], 1).subsumes(&clue));
+  }
 }
 
 /// Testing generic constraint compatability with [`System`] solving
 ///
 /// [`System`]: crate::system::System
 mod solver {
+  use std::cell::Cell;
   use std::collections::HashMap;
   use std::collections::HashSet;
 
+  use crate::assignment::Assignment;
+  use crate::constraint::{Branching, Constraint};
   use crate::prelude::MineConstraint;
   use crate::prelude::System;
+  use crate::system::Propagation;
   use crate::systems::mines::assignment::MineAssignment;
+  use crate::systems::mines::topology::{Square, Topology};
 
   #[test]
   fn unresolvable() {
@@ -248,4 +416,1311 @@ mod solver {
       ])
     );
   }
+
+  /// Constraints can be removed and re-inserted after others have been removed
+  #[test]
+  fn remove_reinsert() {
+    let cons0 = MineConstraint::new([0, 1], 1);
+    let cons1 = MineConstraint::new([2, 3], 1);
+    let cons2 = MineConstraint::new([4, 5], 1);
+
+    let mut sys = System::from([cons0.clone(), cons1.clone(), cons2.clone()]);
+    assert_eq!(sys.remove(&cons0), Some(cons0.clone()));
+    assert_eq!(sys.remove(&cons2), Some(cons2.clone()));
+    assert_eq!(sys.remove(&cons0), None);
+    assert_eq!(Vec::from_iter(&sys), vec![&cons1]);
+
+    assert!(!sys.insert(cons0.clone()));
+    assert_eq!(sys.len(), 2);
+  }
+
+  /// Constraints can be inserted after the system has been fully solved
+  #[test]
+  fn insert_after_solved() {
+    let cons = MineConstraint::new([0, 1], 0);
+
+    let mut sys = System::from([cons.clone()]);
+    sys.pop_solution().unwrap();
+    assert!(sys.is_empty());
+
+    assert!(!sys.insert(cons));
+    let sltn = sys.pop_solution().unwrap();
+    assert_eq!(
+      HashMap::from_iter(sltn),
+      HashMap::from([(0, false), (1, false)])
+    );
+  }
+
+  #[test]
+  fn verify() {
+    let cons0 = MineConstraint::new([0, 1, 2], 2);
+    let cons1 = MineConstraint::new([1, 2, 3], 1);
+    let sys = System::from([cons0.clone(), cons1.clone()]);
+
+    // every solution found by the system verifies against it
+    for sltn in sys.clone().solve() {
+      assert!(sys.verify(&sltn).is_ok());
+    }
+
+    let violations = sys
+      .verify(&MineAssignment::new([0, 3], [1, 2]))
+      .unwrap_err();
+    assert_eq!(violations.violated, vec![&cons1]);
+    assert!(violations.unassigned.is_empty());
+
+    // both constraints can still be satisfied by tiles 2 and 3
+    let violations = sys.verify(&MineAssignment::new([0], [1])).unwrap_err();
+    assert!(violations.violated.is_empty());
+    assert_eq!(violations.unassigned, HashSet::from([2, 3]));
+
+    // cons1 already has too many mines, whatever tile 3 is
+    let violations = sys.verify(&MineAssignment::new([0], [1, 2])).unwrap_err();
+    assert_eq!(violations.violated, vec![&cons1]);
+    assert_eq!(violations.unassigned, HashSet::from([3]));
+  }
+
+  /// Probing finds tiles forced by chains of constraints that don't reduce each other
+  #[test]
+  fn probing() {
+    let constraints = [
+      MineConstraint::new([0, 1], 1),
+      MineConstraint::new([1, 2], 1),
+      MineConstraint::new([0, 2, 3], 1),
+    ];
+
+    let mut sys = System::from(constraints.clone());
+    let sltn = sys.pop_solution().unwrap();
+    assert_eq!(HashMap::from_iter(sltn), HashMap::new());
+
+    // a mine at 0 forces a mine at 2, which gives 0, 2, 3 too many mines
+    let mut sys = System::from(constraints).with_propagation(Propagation::Probing);
+    let sltn = sys.pop_propagated_solution().unwrap();
+    assert_eq!(
+      HashMap::from_iter(sltn),
+      HashMap::from([(0, false), (1, true), (2, false), (3, true)])
+    );
+    assert!(sys.is_empty());
+  }
+
+  #[test]
+  fn probing_conflicts() {
+    // each pair of tiles has exactly one mine, which no placement satisfies
+    let constraints = [
+      MineConstraint::new([0, 1], 1),
+      MineConstraint::new([1, 2], 1),
+      MineConstraint::new([0, 2], 1),
+    ];
+
+    let mut sys = System::from(constraints.clone());
+    assert!(sys.pop_solution().is_ok());
+
+    let mut sys = System::from(constraints).with_propagation(Propagation::Probing);
+    assert!(sys.pop_propagated_solution().is_err());
+  }
+
+  #[test]
+  fn simplify() {
+    let mut sys = System::from([
+      MineConstraint::new([0, 1], 2),
+      MineConstraint::new([0], 1),
+      MineConstraint::new([1, 2, 3], 1),
+      MineConstraint::new([1], 1),
+      MineConstraint::new([4, 5], 1),
+    ]);
+
+    let removed: HashSet<_> = sys.simplify().into_iter().collect();
+    assert_eq!(
+      removed,
+      HashSet::from([MineConstraint::new([0], 1), MineConstraint::new([1], 1)])
+    );
+    assert_eq!(sys.len(), 3);
+
+    // removing subsumed constraints doesn't change the solutions
+    let sltn = sys.pop_solution().unwrap();
+    assert_eq!(
+      HashMap::from_iter(sltn),
+      HashMap::from([(0, true), (1, true), (2, false), (3, false)])
+    );
+    assert_eq!(sys.simplify(), vec![]);
+  }
+
+  /// Each solution is only found once
+  #[test]
+  fn unique_solutions() {
+    let board = Square::new(5, 4);
+    let sys = board.system([
+      ((0, 0), 1),
+      ((2, 0), 2),
+      ((4, 0), 1),
+      ((0, 3), 0),
+      ((4, 3), 1),
+    ]);
+
+    let count = sys.clone().solve().count();
+    let sltns: HashSet<_> = sys.clone().solve().collect();
+    assert_eq!(count, sltns.len());
+    assert!(count > 1);
+
+    for branching in [Branching::Values, Branching::Binary] {
+      let sys = sys.clone().with_branching(branching);
+      assert_eq!(sys.solve().count(), count);
+    }
+  }
+
+  /// Memoising explored subsystems finds the same solutions
+  #[test]
+  fn transpositions() {
+    // independent regions reach the same subsystems on every branch
+    let board = Square::new(7, 3);
+    let sys = board.system([((0, 0), 2), ((6, 0), 1), ((0, 2), 1), ((6, 2), 2)]);
+
+    let sltns: HashSet<_> = sys.clone().solve().collect();
+    for capacity in [0, 1, 4, 1024] {
+      let memoised: Vec<_> = sys.clone().solve().with_transpositions(capacity).collect();
+      assert_eq!(memoised.len(), sltns.len());
+      assert_eq!(HashSet::from_iter(memoised), sltns);
+    }
+
+    // unsatisfiable subsystems stay unsatisfiable
+    let sys = System::from([
+      MineConstraint::new([0, 1, 2], 1),
+      MineConstraint::new([3, 4], 1),
+      MineConstraint::new([4, 5], 1),
+      MineConstraint::new([3, 5], 1),
+    ]);
+    assert_eq!(sys.solve().with_transpositions(1024).count(), 0);
+  }
+
+  /// Subsystems reached again through other branches aren't explored again
+  #[test]
+  fn transposition_hits() {
+    // every branch on one region leaves the same regions to solve
+    let sys = System::from([
+      MineConstraint::new([0, 1], 1),
+      MineConstraint::new([2, 3], 1),
+      MineConstraint::new([4, 5], 1),
+      MineConstraint::new([6, 7], 1),
+    ]);
+
+    // the heuristic ranks each constraint of every subsystem explored
+    let ranked = Cell::new(0);
+    let heuristic = |cons: &MineConstraint<_>, _: &[&MineConstraint<_>]| {
+      ranked.set(ranked.get() + 1);
+      cons.size()
+    };
+
+    let sltns: HashSet<_> = sys.clone().solve_with(heuristic).collect();
+    assert_eq!(sltns.len(), 16);
+    assert_eq!(ranked.replace(0), 4 + 2 * 3 + 4 * 2 + 8);
+
+    let memoised: Vec<_> = sys.solve_with(heuristic).with_transpositions(64).collect();
+    assert_eq!(HashSet::from_iter(memoised), sltns);
+    assert_eq!(ranked.get(), 4 + 3 + 2 + 1);
+  }
+
+  /// Solutions are found in lexicographic order
+  #[test]
+  fn ordered() {
+    let board = Square::new(5, 4);
+    let sys = board.system([((0, 0), 1), ((2, 0), 2), ((4, 0), 1), ((4, 3), 1)]);
+    let vars: Vec<_> = sys.iter().flat_map(|cons| cons.tiles().clone()).collect();
+    let vars: Vec<_> = vars.into_iter().rev().collect();
+
+    let sltns: HashSet<_> = sys.clone().solve().collect();
+    let ordered: Vec<_> = sys
+      .clone()
+      .solve_ordered(vars.clone(), [true, false])
+      .collect();
+    assert_eq!(ordered.len(), sltns.len());
+    assert_eq!(HashSet::from_iter(ordered.clone()), sltns);
+
+    let key = |sltn: &MineAssignment<_>| -> Vec<_> {
+      let mut seen = HashSet::new();
+      vars
+        .iter()
+        .filter(|&var| seen.insert(var))
+        .map(|var| sltn.get(var).map(|&value| !value))
+        .collect()
+    };
+    for pair in ordered.windows(2) {
+      assert!(key(&pair[0]) < key(&pair[1]));
+    }
+
+    for (k, sltn) in ordered.iter().enumerate() {
+      let kth = sys.clone().kth_solution(vars.clone(), [true, false], k);
+      assert_eq!(kth.as_ref(), Some(sltn));
+    }
+    assert_eq!(sys.kth_solution(vars, [true, false], ordered.len()), None);
+  }
+}
+
+/// Testing guess recommendations when no tile is known to be safe
+mod guesses {
+  use std::collections::HashMap;
+
+  use crate::prelude::{MineConstraint, System};
+  use crate::systems::mines::guess::GuessAdvisor;
+
+  /// Tiles in a line, where each tile neighbours the tiles either side of it
+  fn line(tile: &i32) -> [i32; 2] {
+    [tile - 1, tile + 1]
+  }
+
+  #[test]
+  fn probabilities() {
+    let sys = System::from([MineConstraint::new([0, 1], 1)]);
+    let advisor = GuessAdvisor::new(sys, 0..6, 2, line);
+
+    assert_eq!(
+      advisor.probabilities(),
+      HashMap::from([
+        (0, 0.5),
+        (1, 0.5),
+        (2, 0.25),
+        (3, 0.25),
+        (4, 0.25),
+        (5, 0.25)
+      ])
+    );
+  }
+
+  /// Frontier solutions with fewer mines leave more ways to place the rest
+  #[test]
+  fn global_count() {
+    let sys = System::from([
+      MineConstraint::new([0, 1], 1),
+      MineConstraint::new([1, 2], 1),
+    ]);
+    let advisor = GuessAdvisor::new(sys, 0..6, 2, line);
+
+    let probs = advisor.probabilities();
+    assert_eq!(probs[&0], 0.25);
+    assert_eq!(probs[&1], 0.75);
+    assert_eq!(probs[&2], 0.25);
+    assert_eq!(probs[&3], 0.25);
+    assert_eq!(probs.values().sum::<f64>(), 2.0);
+  }
+
+  /// Equally risky tiles are ranked by how likely they are to give new deductions
+  #[test]
+  fn tie_break() {
+    let sys = System::from([
+      MineConstraint::new([0, 1], 1),
+      MineConstraint::new([1, 2], 1),
+    ]);
+    let advisor = GuessAdvisor::new(sys, 0..6, 2, line);
+
+    let guesses = advisor.rank();
+    assert_eq!(guesses.len(), 6);
+    assert_eq!(guesses.last().unwrap().tile, 1);
+
+    let tiles: Vec<_> = guesses.iter().map(|guess| guess.tile).collect();
+    for tile in [0, 2, 5] {
+      let guess = &guesses[tiles.iter().position(|&t| t == tile).unwrap()];
+      assert_eq!(guess.progress_chance, 1.0);
+    }
+    assert!(tiles[..3].iter().all(|tile| [0, 2, 5].contains(tile)));
+
+    let best = advisor.best().unwrap();
+    assert_eq!(best.mine_chance, 0.25);
+    assert_eq!(best.progress_chance, 1.0);
+  }
+
+  /// Tiles that are already known to be safe are always recommended first
+  #[test]
+  fn decided() {
+    let sys = System::from([MineConstraint::new([0, 1], 1), MineConstraint::new([1], 1)]);
+    let advisor = GuessAdvisor::new(sys, 0..4, 2, line);
+
+    let best = advisor.best().unwrap();
+    assert_eq!((best.tile, best.mine_chance), (0, 0.0));
+  }
+
+  #[test]
+  fn inconsistent() {
+    let sys = System::from([MineConstraint::new([0, 1], 2)]);
+    let advisor = GuessAdvisor::new(sys, 0..4, 1, line);
+
+    assert!(advisor.probabilities().is_empty());
+    assert_eq!(advisor.best(), None);
+  }
+}
+
+/// Testing exhaustive search of minesweeper endgames
+mod endgames {
+  use crate::prelude::{MineConstraint, System};
+  use crate::systems::mines::endgame::{EndgameSolver, MAX_TILES};
+
+  /// Tiles in a line, where each tile neighbours the tiles either side of it
+  fn line(tile: &i32) -> [i32; 2] {
+    [tile - 1, tile + 1]
+  }
+
+  /// Tiles that give no information when revealed
+  fn isolated(_: &i32) -> [i32; 0] {
+    []
+  }
+
+  /// A forced guess between 2 tiles can only be won half of the time
+  #[test]
+  fn coin_flip() {
+    let sys = System::from([MineConstraint::new([0, 1], 1)]);
+    let solver = EndgameSolver::new(sys, [0, 1], 1, isolated);
+
+    let best = solver.solve().unwrap();
+    assert_eq!(best.win_chance, 0.5);
+  }
+
+  /// Equally risky tiles can give more or less useful clues
+  #[test]
+  fn informative() {
+    let solver = EndgameSolver::new(System::default(), 0..3, 1, line);
+
+    let best = solver.solve().unwrap();
+    assert_ne!(best.tile, 1);
+    assert!((best.win_chance - 2.0 / 3.0).abs() < 1e-9);
+  }
+
+  /// Tiles known to be safe are revealed first
+  #[test]
+  fn forced() {
+    let sys = System::from([
+      MineConstraint::new([0, 1], 1),
+      MineConstraint::new([1, 2], 1),
+    ]);
+    let solver = EndgameSolver::new(sys, 0..3, 1, line);
+
+    let best = solver.solve().unwrap();
+    assert_eq!(best.win_chance, 1.0);
+  }
+
+  #[test]
+  fn won() {
+    let sys = System::from([MineConstraint::new([0], 1)]);
+    let solver = EndgameSolver::new(sys, [0], 1, line);
+    assert_eq!(solver.solve(), None);
+  }
+
+  /// Every first reveal hits the mine a quarter of the time,\
+  /// but any safe reveal leaves enough clues to find the mine without guessing again.
+  #[test]
+  fn exact() {
+    let solver = EndgameSolver::new(System::default(), 0..4, 1, line);
+
+    let best = solver.solve().unwrap();
+    assert!((best.win_chance - 3.0 / 4.0).abs() < 1e-12);
+  }
+
+  /// Isolated tiles never give clues, so the game is only won by guessing the exact placement:\
+  /// 3 ways to place 1 mine among tiles 0..3, and 9 ways to leave 1 of the other tiles safe.
+  #[test]
+  fn largest() {
+    let sys = System::from([MineConstraint::new([0, 1, 2], 1)]);
+    let solver = EndgameSolver::new(sys, 0..MAX_TILES as i32, 9, isolated);
+
+    let best = solver.solve().unwrap();
+    assert!((best.win_chance - 1.0 / 27.0).abs() < 1e-12);
+  }
+
+  #[test]
+  fn too_large() {
+    let tiles = 0..(MAX_TILES as i32 + 1);
+    let solver = EndgameSolver::new(System::default(), tiles, 1, line);
+    assert_eq!(solver.solve(), None);
+  }
+
+  /// Tiles only mentioned by the clues count towards the unknown tiles
+  #[test]
+  fn too_large_with_clues() {
+    let clue = MAX_TILES as i32;
+    let sys = System::from([MineConstraint::new([clue - 1, clue], 1)]);
+    let solver = EndgameSolver::new(sys, 0..clue, 1, line);
+    assert_eq!(solver.solve(), None);
+  }
+}
+
+/// Testing generation of boards that can be solved without guessing
+mod generation {
+  use crate::systems::mines::generator::{Generator, Layout};
+
+  #[test]
+  fn neighbours() {
+    let layout = Layout::new(3, 3, []);
+    assert_eq!(layout.neighbours(&(1, 1)).len(), 8);
+    assert_eq!(layout.neighbours(&(0, 0)), vec![(1, 0), (0, 1), (1, 1)]);
+  }
+
+  #[test]
+  fn clue() {
+    let layout = Layout::new(3, 3, [(0, 0), (2, 2)]);
+    assert_eq!(layout.clue(&(1, 1)), 2);
+    assert_eq!(layout.clue(&(2, 0)), 0);
+  }
+
+  #[test]
+  fn solvable() {
+    let layout = Layout::new(3, 1, [(2, 0)]);
+    assert!(layout.solvable_from((0, 0)));
+  }
+
+  /// A single clue shared between 3 tiles requires a guess
+  #[test]
+  fn unsolvable() {
+    let layout = Layout::new(2, 2, [(1, 1)]);
+    assert!(!layout.solvable_from((0, 0)));
+    assert!(!layout.solvable_from((1, 1)));
+  }
+
+  #[test]
+  fn generate() {
+    for seed in 0..5 {
+      let mut generator = Generator::new(8, 8, 0.15, seed);
+      let layout = generator.generate((3, 3)).unwrap();
+
+      assert_eq!((layout.width(), layout.height()), (8, 8));
+      assert_eq!(layout.mines().len(), 10);
+      assert_eq!(layout.clue(&(3, 3)), 0);
+      assert!(!layout.is_mine(&(3, 3)));
+      assert!(layout.solvable_from((3, 3)));
+    }
+  }
+
+  /// Generating with the same seed gives the same board
+  #[test]
+  fn seeded() {
+    let layout0 = Generator::new(10, 6, 0.15, 7).generate((0, 0));
+    let layout1 = Generator::new(10, 6, 0.15, 7).generate((0, 0));
+    assert!(layout0.is_some());
+    assert_eq!(layout0, layout1);
+  }
+
+  /// Densities that leave no room for the first move are capped
+  #[test]
+  fn dense() {
+    let mut generator = Generator::new(3, 3, 1.0, 0);
+    let layout = generator.generate((1, 1)).unwrap();
+    assert_eq!(layout.mines().len(), 8);
+  }
+}
+
+/// Testing difficulty ratings of boards
+mod ratings {
+  use std::collections::{HashMap, HashSet};
+
+  use crate::prelude::MineConstraint;
+  use crate::systems::mines::generator::{Generator, Layout};
+  use crate::systems::mines::rating::{Technique, deduce, rate};
+  use crate::systems::mines::topology::{Square, Torus};
+
+  #[test]
+  fn trivial() {
+    let clues = [
+      MineConstraint::new([0, 1], 0),
+      MineConstraint::new([2, 3], 1),
+    ];
+    let step = deduce(&clues, &HashSet::from([0, 1, 2, 3]), 1).unwrap();
+
+    assert_eq!(step.technique, Technique::Trivial);
+    assert_eq!(
+      HashMap::from_iter(step.deduced),
+      HashMap::from([(0, false), (1, false)])
+    );
+  }
+
+  #[test]
+  fn subset() {
+    let clues = [
+      MineConstraint::new([0, 1], 1),
+      MineConstraint::new([0, 1, 2], 2),
+    ];
+    let step = deduce(&clues, &HashSet::from([0, 1, 2, 3]), 3).unwrap();
+
+    assert_eq!(step.technique, Technique::Subset);
+    assert_eq!(HashMap::from_iter(step.deduced), HashMap::from([(2, true)]));
+  }
+
+  /// The 1-2-1 pattern can't be solved by pairwise reduction
+  #[test]
+  fn overlap() {
+    let clues = [
+      MineConstraint::new([0, 1, 2], 1),
+      MineConstraint::new([1, 2, 3], 2),
+      MineConstraint::new([2, 3, 4], 1),
+    ];
+    let step = deduce(&clues, &HashSet::from([0, 1, 2, 3, 4, 5]), 3).unwrap();
+
+    assert_eq!(step.technique, Technique::Overlap);
+    assert_eq!(
+      HashMap::from_iter(step.deduced),
+      HashMap::from([(0, false), (1, true), (2, false), (3, true), (4, false)])
+    );
+  }
+
+  #[test]
+  fn global_count() {
+    let clues = [MineConstraint::new([0, 1], 1)];
+    let step = deduce(&clues, &HashSet::from([0, 1, 2, 3]), 1).unwrap();
+
+    assert_eq!(step.technique, Technique::GlobalCount);
+    assert_eq!(
+      HashMap::from_iter(step.deduced),
+      HashMap::from([(2, false), (3, false)])
+    );
+  }
+
+  #[test]
+  fn guess() {
+    let clues = [MineConstraint::new([0, 1], 1)];
+    assert_eq!(deduce(&clues, &HashSet::from([0, 1]), 1), None);
+  }
+
+  #[test]
+  fn rate_solvable() {
+    let rating = Layout::new(4, 1, [(3, 0)]).rate((0, 0));
+
+    assert!(rating.solved);
+    assert_eq!(rating.hardest(), Some(Technique::Trivial));
+    // the mine never needs to be flagged, as all safe tiles are revealed
+    assert_eq!(rating.steps.len(), 2);
+    assert_eq!(rating.score(), 2);
+  }
+
+  #[test]
+  fn rate_unsolvable() {
+    let rating = Layout::new(2, 2, [(1, 1)]).rate((0, 0));
+
+    assert!(!rating.solved);
+    assert!(rating.steps.is_empty());
+  }
+
+  /// Generated boards never need a guess
+  #[test]
+  fn rate_generated() {
+    let layout = Generator::new(8, 8, 0.15, 3).generate((0, 0)).unwrap();
+    let rating = layout.rate((0, 0));
+
+    assert!(rating.solved);
+    assert!(rating.hardest() <= Some(Technique::GlobalCount));
+  }
+
+  /// Wrapped edges give the clues more tiles to count
+  #[test]
+  fn rate_torus() {
+    let mines = HashSet::from([(2, 0)]);
+    let square = rate(&Square::new(4, 1), &mines, (0, 0));
+    let torus = rate(&Torus::new(4, 1), &mines, (0, 0));
+
+    assert!(square.solved);
+    assert_eq!(square.hardest(), Some(Technique::GlobalCount));
+    // the first clue sees the mine on both sides of the tile
+    assert!(torus.solved);
+    assert_eq!(torus.hardest(), Some(Technique::Trivial));
+  }
+}
+
+/// Testing explanations for why tiles were decided
+mod explanations {
+  use std::collections::{HashMap, HashSet};
+
+  use crate::explain::Derivation;
+  use crate::prelude::{Assignment, MineConstraint, System};
+
+  #[test]
+  fn given() {
+    let cons = MineConstraint::new([0, 1], 0);
+    let mut sys = System::from([cons.clone()]).explained();
+
+    let sltn = sys.pop_solution().unwrap();
+    assert_eq!(
+      HashMap::from_iter(sltn.solution().clone()),
+      HashMap::from([(0, false), (1, false)])
+    );
+    for tile in [0, 1] {
+      let reason = sltn.reason(&tile).unwrap();
+      assert!(matches!(reason, Derivation::Given(_)));
+      assert_eq!(reason.premises(), vec![&cons]);
+    }
+  }
+
+  /// A tile is safe because a clue is satisfied by a known mine
+  #[test]
+  fn reduced() {
+    let clue = MineConstraint::new([0, 1], 1);
+    let mine = MineConstraint::new([0], 1);
+    let mut sys = System::from([clue.clone(), mine.clone()]).explained();
+
+    let sltn = sys.pop_solution().unwrap();
+    assert_eq!(sltn.reason(&0).unwrap().premises(), vec![&mine]);
+
+    let reason = sltn.reason(&1).unwrap();
+    assert_eq!(reason.premises(), vec![&clue, &mine]);
+    assert_eq!(
+      reason.reductions(),
+      vec![(&clue, &mine, &MineConstraint::new([1], 0))]
+    );
+  }
+
+  /// Reductions are chained through intermediate constraints
+  #[test]
+  fn chained() {
+    let cons0 = MineConstraint::new([0, 1, 2], 2);
+    let cons1 = MineConstraint::new([1, 2], 1);
+    let cons2 = MineConstraint::new([0, 3], 1);
+    let mut sys = System::from([cons0.clone(), cons1.clone(), cons2.clone()]).explained();
+
+    let sltn = sys.pop_solution().unwrap();
+    let reason = sltn.reason(&3).unwrap();
+    assert_eq!(
+      HashSet::<_>::from_iter(reason.premises()),
+      HashSet::from([&cons0, &cons1, &cons2])
+    );
+    assert_eq!(reason.constraint(), &MineConstraint::new([3], 0));
+  }
+
+  /// Explained systems have the same solutions as unexplained ones
+  #[test]
+  fn solutions() {
+    let cons0 = MineConstraint::new([0, 1, 2], 2);
+    let cons1 = MineConstraint::new([1, 2], 1);
+
+    let sys = System::from([cons0, cons1]);
+    let sltns: HashSet<_> = sys.clone().solve().collect();
+    let explained: HashSet<_> = sys
+      .explained()
+      .solve()
+      .map(|sltn| sltn.into_solution())
+      .collect();
+    assert_eq!(sltns, explained);
+  }
+
+  #[test]
+  fn restricted() {
+    let clue = MineConstraint::new([0, 1], 1);
+    let mine = MineConstraint::new([0], 1);
+    let mut sys = System::from([clue, mine]).explained();
+
+    let sltn = sys.pop_solution().unwrap().restrict_to([1]);
+    assert_eq!(sltn.len(), 1);
+    assert_eq!(sltn.get(&1), Some(&false));
+    assert!(sltn.reason(&0).is_none());
+    assert!(sltn.reason(&1).is_some());
+  }
+}
+
+/// Testing board topologies and the constraints they generate
+mod topologies {
+  use std::collections::{HashMap, HashSet};
+
+  use crate::prelude::MineConstraint;
+  use crate::systems::mines::topology::{Cube, Custom, Hex, Square, Topology, Torus};
+
+  #[test]
+  fn square() {
+    let board = Square::new(4, 3);
+    assert_eq!(board.tiles().count(), 12);
+    assert_eq!(board.neighbours(&(1, 1)).len(), 8);
+    assert_eq!(board.neighbours(&(3, 2)).len(), 3);
+    assert_eq!(board.neighbours(&(0, 1)).len(), 5);
+  }
+
+  #[test]
+  fn torus() {
+    let board = Torus::new(4, 3);
+    assert_eq!(board.tiles().count(), 12);
+    assert_eq!(
+      HashSet::<_>::from_iter(board.neighbours(&(0, 0))),
+      HashSet::from([
+        (3, 2),
+        (0, 2),
+        (1, 2),
+        (3, 0),
+        (1, 0),
+        (3, 1),
+        (0, 1),
+        (1, 1)
+      ])
+    );
+
+    // tiles aren't repeated when the board wraps onto itself
+    let board = Torus::new(2, 1);
+    assert_eq!(board.neighbours(&(0, 0)), vec![(1, 0)]);
+
+    // empty boards have no neighbours
+    let board = Torus::new(0, 3);
+    assert_eq!(board.tiles().count(), 0);
+    assert_eq!(board.neighbours(&(0, 0)), vec![]);
+  }
+
+  #[test]
+  fn hex() {
+    let board = Hex::new(4, 4);
+    assert_eq!(
+      HashSet::<_>::from_iter(board.neighbours(&(1, 1))),
+      HashSet::from([(0, 1), (2, 1), (1, 0), (2, 0), (1, 2), (2, 2)])
+    );
+    assert_eq!(
+      HashSet::<_>::from_iter(board.neighbours(&(1, 2))),
+      HashSet::from([(0, 2), (2, 2), (0, 1), (1, 1), (0, 3), (1, 3)])
+    );
+    assert_eq!(board.neighbours(&(0, 0)).len(), 2);
+  }
+
+  #[test]
+  fn cube() {
+    let board = Cube::new(3, 3, 3);
+    assert_eq!(board.tiles().count(), 27);
+    assert_eq!(board.neighbours(&(1, 1, 1)).len(), 26);
+    assert_eq!(board.neighbours(&(0, 0, 0)).len(), 7);
+  }
+
+  #[test]
+  fn custom() {
+    let ring = Custom::new(0..5, |&tile: &usize| [(tile + 4) % 5, (tile + 1) % 5]);
+    assert_eq!(ring.tiles().count(), 5);
+    assert_eq!(ring.neighbours(&0), vec![4, 1]);
+  }
+
+  #[test]
+  fn clue() {
+    let board = Hex::new(3, 3);
+    assert_eq!(
+      board.clue(&(0, 0), 1),
+      MineConstraint::new([(1, 0), (0, 1)], 1)
+    );
+  }
+
+  /// Revealed clues produce a solvable system on any topology
+  #[test]
+  fn system() {
+    let board = Torus::new(3, 3);
+    let revealed = board
+      .tiles()
+      .filter(|&tile| tile != (2, 2))
+      .map(|tile| (tile, 1));
+
+    let mut sys = board.system(revealed);
+    let sltn = sys.pop_solution().unwrap();
+    let sltn: HashMap<_, _> = sltn.into_iter().collect();
+    assert!(sltn[&(2, 2)]);
+  }
+}
+
+/// Testing boards that update their constraints as tiles are revealed
+mod boards {
+  use std::collections::{HashMap, HashSet};
+
+  use crate::prelude::MineConstraint;
+  use crate::systems::mines::board::MineBoard;
+  use crate::systems::mines::errors::{MineConflicts, ReductionCase};
+  use crate::systems::mines::generator::Layout;
+  use crate::systems::mines::topology::{Square, Topology};
+
+  #[test]
+  fn empty() {
+    let board = MineBoard::new(Square::new(2, 2));
+    assert!(board.safe().is_empty());
+    assert!(board.mines().is_empty());
+    assert_eq!(board.unknown().len(), 4);
+    assert!(board.system().is_empty());
+  }
+
+  /// Revealing a zero decides all of its neighbours
+  #[test]
+  fn reveal_zero() {
+    let mut board = MineBoard::new(Square::new(3, 3));
+    let decided = board.reveal((0, 0), 0).unwrap();
+
+    assert_eq!(
+      HashMap::from_iter(decided),
+      HashMap::from([
+        ((0, 0), false),
+        ((1, 0), false),
+        ((0, 1), false),
+        ((1, 1), false),
+      ])
+    );
+    assert_eq!(board.unrevealed_safe().count(), 3);
+    assert_eq!(board.unknown().len(), 5);
+  }
+
+  /// Flags are taken away from the clues of revealed tiles
+  #[test]
+  fn flag() {
+    let mut board = MineBoard::new(Square::new(3, 1));
+    board.flag((0, 0)).unwrap();
+    let decided = board.reveal((1, 0), 1).unwrap();
+
+    assert_eq!(
+      HashMap::from_iter(decided),
+      HashMap::from([((1, 0), false), ((2, 0), false)])
+    );
+    assert_eq!(board.mines(), &HashSet::from([(0, 0)]));
+    assert!(board.unknown().is_empty());
+  }
+
+  #[test]
+  fn conflicts() {
+    let mut board = MineBoard::new(Square::new(3, 1));
+    board.reveal((0, 0), 0).unwrap();
+    assert_eq!(
+      board.flag((1, 0)),
+      Err(MineConflicts::Reduction {
+        constraint: MineConstraint::new([(1, 0)], 1),
+        other: MineConstraint::new([(1, 0)], 0),
+        case: ReductionCase::AllSafe,
+      })
+    );
+
+    // revealing a flagged tile reports the flag
+    let mut board = MineBoard::new(Square::new(3, 1));
+    board.flag((0, 0)).unwrap();
+    assert_eq!(
+      board.reveal((0, 0), 1),
+      Err(MineConflicts::Reduction {
+        constraint: MineConstraint::new([(0, 0)], 0),
+        other: MineConstraint::new([(0, 0)], 1),
+        case: ReductionCase::AllMine,
+      })
+    );
+
+    let mut board = MineBoard::new(Square::new(3, 1));
+    board.flag((0, 0)).unwrap();
+    board.flag((2, 0)).unwrap();
+    assert_eq!(
+      board.reveal((1, 0), 1),
+      Err(MineConflicts::Reduction {
+        constraint: MineConstraint::new([(0, 0), (2, 0)], 1),
+        other: MineConstraint::new([(0, 0), (2, 0)], 2),
+        case: ReductionCase::AllMine,
+      })
+    );
+  }
+
+  /// Revealing a tile again must show the same clue
+  #[test]
+  fn reveal_again() {
+    let mut board = MineBoard::new(Square::new(3, 1));
+    board.reveal((0, 0), 1).unwrap();
+    assert_eq!(board.reveal((0, 0), 1), Ok(Default::default()));
+
+    assert_eq!(
+      board.reveal((0, 0), 0),
+      Err(MineConflicts::Reduction {
+        constraint: MineConstraint::new([(1, 0)], 0),
+        other: MineConstraint::new([(1, 0)], 1),
+        case: ReductionCase::AllMine,
+      })
+    );
+    assert_eq!(board.revealed(), &HashMap::from([((0, 0), 1)]));
+
+    // clues that conflict aren't remembered
+    let mut board = MineBoard::new(Square::new(3, 1));
+    board.flag((0, 0)).unwrap();
+    assert!(board.reveal((1, 0), 0).is_err());
+    assert!(board.revealed().is_empty());
+  }
+
+  /// Playing a board by revealing every tile found to be safe decides the whole board
+  #[test]
+  fn play() {
+    let layout = Layout::new(4, 4, [(3, 0), (0, 3)]);
+    let mut board = MineBoard::new(Square::new(4, 4));
+
+    let mut queue = vec![(1, 1)];
+    while let Some(tile) = queue.pop() {
+      board.reveal(tile, layout.clue(&tile)).unwrap();
+      queue.extend(board.unrevealed_safe().copied());
+    }
+
+    assert!(board.unknown().is_empty());
+    assert_eq!(board.mines(), layout.mines());
+    assert_eq!(
+      board.safe().len() + board.mines().len(),
+      Square::new(4, 4).tiles().count()
+    );
+  }
+}
+
+/// Testing reading and writing boards in interchange formats
+mod formats {
+  use std::collections::{HashMap, HashSet};
+
+  use crate::systems::mines::formats::{FormatError, Game, json, mbf, text};
+  use crate::systems::mines::generator::{Generator, Layout};
+
+  fn game() -> Game {
+    Game::new(Layout::new(3, 2, [(2, 0)]), [(0, 0), (0, 1), (1, 1)]).unwrap()
+  }
+
+  #[test]
+  fn text_read() {
+    let game = text::read("01*\r\n011\n\n").unwrap();
+    assert_eq!(game.layout, Layout::new(3, 2, [(2, 0)]));
+    assert_eq!(game.revealed.len(), 5);
+  }
+
+  #[test]
+  fn text_roundtrip() {
+    let game = game();
+    assert_eq!(text::write(&game), "0.*\n01.\n");
+    assert_eq!(text::read(&text::write(&game)), Ok(game));
+  }
+
+  #[test]
+  fn text_errors() {
+    assert_eq!(text::read(""), Err(FormatError::UnexpectedEnd));
+    assert_eq!(
+      text::read("..\n.x\n"),
+      Err(FormatError::InvalidSymbol {
+        line: 1,
+        column: 1,
+        symbol: 'x'
+      })
+    );
+    assert_eq!(
+      text::read("...\n..\n"),
+      Err(FormatError::RaggedRow { line: 1 })
+    );
+    assert_eq!(
+      text::read("2*\n"),
+      Err(FormatError::WrongClue {
+        tile: (0, 0),
+        shown: 2,
+        actual: 1
+      })
+    );
+  }
+
+  #[test]
+  fn mbf_roundtrip() {
+    let layout = Generator::new(16, 16, 0.15, 7).generate((0, 0)).unwrap();
+    let game = Game::new(layout, []).unwrap();
+
+    let bytes = mbf::write(&game).unwrap();
+    assert_eq!(bytes.len(), 4 + 2 * game.layout.mines().len());
+    assert_eq!(mbf::read(&bytes), Ok(game));
+  }
+
+  /// Revealed tiles can't be stored in the binary format
+  #[test]
+  fn mbf_discards_revealed() {
+    let bytes = mbf::write(&game()).unwrap();
+    assert_eq!(bytes, [3, 2, 0, 1, 2, 0]);
+    assert!(mbf::read(&bytes).unwrap().revealed.is_empty());
+  }
+
+  #[test]
+  fn mbf_errors() {
+    assert_eq!(mbf::read(&[3, 2, 0]), Err(FormatError::UnexpectedEnd));
+    assert_eq!(mbf::read(&[3, 2, 0, 1, 2]), Err(FormatError::UnexpectedEnd));
+    assert_eq!(
+      mbf::read(&[3, 2, 0, 1, 2, 0, 0]),
+      Err(FormatError::TrailingData { position: 6 })
+    );
+    assert_eq!(
+      mbf::read(&[3, 2, 0, 1, 3, 0]),
+      Err(FormatError::OutOfBounds { tile: (3, 0) })
+    );
+
+    let game = Game::new(Layout::new(256, 1, []), []).unwrap();
+    assert_eq!(mbf::write(&game), Err(FormatError::TooLarge));
+  }
+
+  #[test]
+  fn json_roundtrip() {
+    let game = game();
+    assert_eq!(
+      json::write(&game),
+      r#"{"width":3,"height":2,"mines":[[2,0]],"revealed":[[0,0],[0,1],[1,1]]}"#
+    );
+    assert_eq!(json::read(&json::write(&game)), Ok(game));
+  }
+
+  /// Unknown fields are ignored and revealed tiles are optional
+  #[test]
+  fn json_read() {
+    let input = r#"
+      {
+        "name": "beginner \"easy\" é",
+        "width": 2, "height": 2,
+        "mines": [ [1, 1] ],
+        "meta": { "won": false, "time": 1.5e2, "player": null }
+      }
+    "#;
+    let game = json::read(input).unwrap();
+    assert_eq!(game.layout, Layout::new(2, 2, [(1, 1)]));
+    assert!(game.revealed.is_empty());
+  }
+
+  #[test]
+  fn json_errors() {
+    assert_eq!(
+      json::read(r#"{"width": 2"#),
+      Err(FormatError::UnexpectedEnd)
+    );
+    assert!(matches!(
+      json::read(r#"{"width": 2, "height": 2}"#),
+      Err(FormatError::InvalidJson { .. })
+    ));
+    assert!(matches!(
+      json::read(r#"{"width": -1, "height": 2, "mines": []}"#),
+      Err(FormatError::InvalidJson { .. })
+    ));
+    assert_eq!(
+      json::read(r#"{"width": 2, "height": 2, "mines": [[1, 1]], "revealed": [[1, 1]]}"#),
+      Err(FormatError::RevealedMine { tile: (1, 1) })
+    );
+    assert_eq!(
+      json::read(r#"{"width": 2, "height": 2, "mines": []} []"#),
+      Err(FormatError::TrailingData { position: 39 })
+    );
+
+    // deeply nested values are refused rather than overflowing the stack
+    assert!(matches!(
+      json::read(&"[".repeat(200_000)),
+      Err(FormatError::InvalidJson { position: 16, .. })
+    ));
+    assert_eq!(
+      json::read(r#"{"width": 100000000, "height": 100000000, "mines": []}"#),
+      Err(FormatError::TooLarge)
+    );
+    assert_eq!(
+      json::read(&format!(
+        r#"{{"width": {}, "height": 2, "mines": []}}"#,
+        usize::MAX
+      )),
+      Err(FormatError::TooLarge)
+    );
+  }
+
+  /// Read games produce the constraints given by their revealed clues
+  #[test]
+  fn system() {
+    let game = text::read("0.*\n01.\n").unwrap();
+    let mut sys = game.system();
+    let sltn: HashMap<_, _> = sys.pop_solution().unwrap().into_iter().collect();
+    assert!(!sltn[&(1, 0)]);
+
+    let board = game.board().unwrap();
+    assert!(
+      board
+        .safe()
+        .is_superset(&HashSet::from([(0, 0), (1, 0), (0, 1), (1, 1)]))
+    );
+  }
+}
+
+/// Testing Gaussian elimination over mine constraints
+mod elimination {
+  use std::collections::HashMap;
+
+  use crate::prelude::{MineConstraint, System};
+  use crate::systems::mines::errors::MineConflicts;
+
+  /// Elimination finds tiles that reducing pairs of constraints can't
+  #[test]
+  fn beyond_pairwise() {
+    let constraints = [
+      MineConstraint::new([0, 1], 1),
+      MineConstraint::new([1, 2], 1),
+      MineConstraint::new([0, 2, 3], 2),
+    ];
+
+    let mut sys = System::from(constraints.clone());
+    let sltn = sys.pop_solution().unwrap();
+    assert_eq!(HashMap::from_iter(sltn), HashMap::new());
+
+    let mut sys = System::from(constraints);
+    let sltn = sys.eliminate().unwrap().pop_solution().unwrap();
+    assert_eq!(
+      HashMap::from_iter(sltn),
+      HashMap::from([(0, true), (1, false), (2, true), (3, false)])
+    );
+    assert!(sys.is_empty());
+  }
+
+  /// Undecidable systems are left unchanged
+  #[test]
+  fn undecided() {
+    let mut sys = System::from([
+      MineConstraint::new([0, 1], 1),
+      MineConstraint::new([1, 2], 1),
+    ]);
+    sys.eliminate().unwrap();
+    assert_eq!(sys.len(), 2);
+
+    let sltn = sys.pop_solution().unwrap();
+    assert_eq!(HashMap::from_iter(sltn), HashMap::new());
+  }
+
+  /// Sums of constraints that can't be met by 0/1 tiles conflict
+  #[test]
+  fn conflicts() {
+    let constraints = [
+      MineConstraint::new([0, 1], 1),
+      MineConstraint::new([1, 2], 1),
+      MineConstraint::new([0, 2], 0),
+      MineConstraint::new([0, 1, 2, 3], 3),
+    ];
+    let mut sys = System::from(constraints.clone());
+
+    let Err(MineConflicts::Elimination {
+      constraints: combined,
+    }) = sys.eliminate()
+    else {
+      panic!("elimination should find the conflict");
+    };
+    assert!(combined.len() > 1);
+    assert!(combined.iter().all(|cons| constraints.contains(cons)));
+  }
+
+  /// Dense systems grow large coefficients without overflowing
+  #[test]
+  fn dense() {
+    // a linear congruential generator, for tiles that overlap in no particular pattern
+    let mut state = 1u64;
+    let mut random = move || {
+      state = state
+        .wrapping_mul(6364136223846793005)
+        .wrapping_add(1442695040888963407);
+      state >> 33
+    };
+
+    let tiles = 40;
+    let mines: Vec<bool> = (0..tiles).map(|tile| tile % 3 == 0).collect();
+    let constraints: Vec<_> = (0..tiles)
+      .map(|_| {
+        let tiles: Vec<_> = (0..tiles).filter(|_| random() % 2 == 0).collect();
+        let count = tiles.iter().filter(|&&tile| mines[tile]).count();
+        MineConstraint::new(tiles, count)
+      })
+      .collect();
+
+    let mut sys = System::from_iter(constraints);
+    let sltn = sys.eliminate().unwrap().pop_solution().unwrap();
+    for (tile, mine) in sltn {
+      assert_eq!(mines[tile], mine);
+    }
+  }
+
+  /// Elimination agrees with searching every solution
+  #[test]
+  fn matches_search() {
+    let constraints = [
+      MineConstraint::new([0, 1, 2], 1),
+      MineConstraint::new([2, 3, 4], 2),
+      MineConstraint::new([4, 5], 1),
+      MineConstraint::new([0, 5, 6], 1),
+      MineConstraint::new([1, 3, 6], 1),
+    ];
+
+    let mut sys = System::from(constraints.clone());
+    let eliminated = sys.eliminate().unwrap().pop_solution().unwrap();
+
+    let searched: Vec<HashMap<_, _>> = System::from(constraints)
+      .solve()
+      .map(HashMap::from_iter)
+      .collect();
+    for (tile, mine) in eliminated {
+      assert!(searched.iter().all(|sltn| sltn[&tile] == mine));
+    }
+  }
+}
+
+/// Unit testing conversions between mine and [`DiscreteConstraints`]
+///
+/// [`DiscreteConstraints`]: crate::systems::generic::constraint::DiscreteConstraint
+mod conversions {
+  use std::collections::HashSet;
+
+  use super::super::{assignment::MineAssignment, constraint::MineConstraint};
+  use crate::prelude::{Constraint, DiscreteConstraint, System};
+  use crate::systems::generic::assignment::DiscreteAssignment;
+  use crate::systems::mines::topology::{Square, Topology};
+
+  #[test]
+  fn assignments() {
+    let assign = MineAssignment::from([(0, true), (1, false), (2, true)]);
+    let lowered = DiscreteAssignment::from(assign.clone());
+    assert_eq!(
+      lowered,
+      DiscreteAssignment::from([(0, true), (1, false), (2, true)])
+    );
+    assert_eq!(MineAssignment::from(lowered), assign);
+  }
+
+  #[test]
+  fn constraints() {
+    let cons = MineConstraint::new([0, 1, 2], 2);
+    let lowered = DiscreteConstraint::from(cons.clone());
+    assert_eq!(lowered.size(), cons.size());
+    assert_eq!(
+      lowered.variables().collect::<HashSet<_>>(),
+      HashSet::from([0, 1, 2])
+    );
+    assert_eq!(MineConstraint::try_from(lowered), Ok(cons));
+
+    // too many mines for the tiles, so there's no placement
+    let lowered = DiscreteConstraint::from(MineConstraint::new([0, 1], 3));
+    assert_eq!(lowered.size(), 0);
+    assert_eq!(lowered.variables().count(), 2);
+    // which can't be raised back into a mine constraint
+    assert_eq!(MineConstraint::try_from(lowered.clone()), Err(lowered));
+
+    let empty = DiscreteConstraint::from(MineConstraint::<usize>::new([], 0));
+    assert_eq!(empty.size(), 1);
+  }
+
+  #[test]
+  fn unrepresentable() {
+    // "a" and "b" can both be mines, but not both safe
+    let table = DiscreteConstraint::from_iter([
+      [("a", true), ("b", false)],
+      [("a", false), ("b", true)],
+      [("a", true), ("b", true)],
+    ]);
+    assert_eq!(MineConstraint::try_from(table.clone()), Err(table));
+
+    // only some placements of 1 mine among 3 tiles
+    let table = DiscreteConstraint::from_iter([
+      [("a", true), ("b", false), ("c", false)],
+      [("a", false), ("b", true), ("c", false)],
+    ]);
+    assert_eq!(MineConstraint::try_from(table.clone()), Err(table));
+  }
+
+  /// Solving either form of a board gives the same solutions
+  #[test]
+  fn identical_solutions() {
+    let board = Square::new(4, 3);
+    let mines = board.system([((0, 0), 1), ((1, 0), 2), ((0, 2), 1), ((3, 2), 1)]);
+    let discrete: System<_> = mines
+      .iter()
+      .cloned()
+      .map(DiscreteConstraint::from)
+      .collect();
+
+    let sltns0: HashSet<_> = mines.solve().collect();
+    let sltns1: HashSet<_> = discrete.solve().map(MineAssignment::from).collect();
+    assert!(!sltns0.is_empty());
+    assert_eq!(sltns0, sltns1);
+  }
+
+  /// Mine clues can be mixed with other rules once lowered
+  #[test]
+  fn mixed() {
+    let clue = DiscreteConstraint::from(MineConstraint::new([0, 1, 2], 1));
+    // tiles 0 and 2 either both contain mines or are both safe
+    let rule = DiscreteConstraint::from_iter([[(0, true), (2, true)], [(0, false), (2, false)]]);
+
+    let sltns: Vec<_> = System::from([clue, rule]).solve().collect();
+    assert_eq!(
+      sltns,
+      vec![DiscreteAssignment::from([
+        (0, false),
+        (1, true),
+        (2, false)
+      ])]
+    );
+  }
 }
@@ -0,0 +1,307 @@
+//! Board shapes and neighbourhoods for generating mine constraints from clues
+
+use std::fmt::Debug;
+use std::hash::Hash;
+
+use super::constraint::MineConstraint;
+use crate::system::System;
+
+/// The shape of a minesweeper board,\
+/// deciding which tiles a revealed clue counts mines in.
+pub trait Topology {
+  /// The position of a tile on the board
+  type Tile: Hash + Eq + Clone + Debug;
+
+  /// All tiles on the board
+  fn tiles(&self) -> impl Iterator<Item = Self::Tile>;
+
+  /// The tiles that a clue revealed at `tile` counts mines in.\
+  /// This should never include `tile` itself.
+  fn neighbours(&self, tile: &Self::Tile) -> Vec<Self::Tile>;
+
+  /// The constraint given by revealing a clue
+  ///
+  /// ## Arguments
+  ///
+  /// - `tile`: the tile that was revealed
+  /// - `count`: the number of mines the revealed clue shows
+  fn clue(&self, tile: &Self::Tile, count: usize) -> MineConstraint<Self::Tile> {
+    MineConstraint::new(self.neighbours(tile), count)
+  }
+
+  /// Constructs the system of constraints given by revealed clues.\
+  /// Each revealed tile is also constrained to be safe.
+  ///
+  /// ## Arguments
+  ///
+  /// - `revealed`: pairs of revealed tiles and the clue shown on them
+  ///
+  /// ## Examples
+  ///
+  /// ```
+  /// # use std::collections::HashMap;
+  /// # use farc3::systems::mines::topology::{Square, Topology};
+  /// let board = Square::new(3, 1);
+  /// let mut sys = board.system([((0, 0), 0), ((1, 0), 1)]);
+  ///
+  /// let sltn = sys.pop_solution().unwrap();
+  /// assert_eq!(
+  ///   HashMap::from_iter(sltn),
+  ///   HashMap::from([((0, 0), false), ((1, 0), false), ((2, 0), true)])
+  /// );
+  /// ```
+  fn system(
+    &self,
+    revealed: impl IntoIterator<Item = (Self::Tile, usize)>,
+  ) -> System<MineConstraint<Self::Tile>> {
+    let mut sys = System::default();
+    for (tile, count) in revealed {
+      sys.insert(self.clue(&tile, count));
+      sys.insert(MineConstraint::new([tile], 0));
+    }
+    sys
+  }
+}
+
+/// Shifts a coordinate by `delta`, returning `None` when it leaves `0..bound`
+fn shift(value: usize, delta: isize, bound: usize) -> Option<usize> {
+  value
+    .checked_add_signed(delta)
+    .filter(|&value| value < bound)
+}
+
+/// Shifts a coordinate by `delta`, wrapping around within `0..bound`
+fn wrap(value: usize, delta: isize, bound: usize) -> usize {
+  (value as isize + delta).rem_euclid(bound as isize) as usize
+}
+
+/// All `(x, y)` positions on a rectangular grid, in row order
+fn grid(width: usize, height: usize) -> impl Iterator<Item = (usize, usize)> {
+  (0..height).flat_map(move |y| (0..width).map(move |x| (x, y)))
+}
+
+/// A rectangular board, where each tile neighbours the 8 tiles around it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Square {
+  width: usize,
+  height: usize,
+}
+
+impl Square {
+  /// Constructs a rectangular board with the given number of columns and rows
+  pub fn new(width: usize, height: usize) -> Self {
+    Self { width, height }
+  }
+}
+
+impl Topology for Square {
+  type Tile = (usize, usize);
+
+  fn tiles(&self) -> impl Iterator<Item = Self::Tile> {
+    grid(self.width, self.height)
+  }
+
+  fn neighbours(&self, &(x, y): &Self::Tile) -> Vec<Self::Tile> {
+    let mut tiles = vec![];
+    for dy in -1..=1 {
+      for dx in -1..=1 {
+        let Some(y0) = shift(y, dy, self.height) else {
+          continue;
+        };
+        let Some(x0) = shift(x, dx, self.width) else {
+          continue;
+        };
+        if (x0, y0) != (x, y) {
+          tiles.push((x0, y0));
+        }
+      }
+    }
+    tiles
+  }
+}
+
+/// A rectangular board where the edges wrap around,\
+/// so that each tile neighbours the 8 tiles around it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Torus {
+  width: usize,
+  height: usize,
+}
+
+impl Torus {
+  /// Constructs a wrapping board with the given number of columns and rows
+  pub fn new(width: usize, height: usize) -> Self {
+    Self { width, height }
+  }
+}
+
+impl Topology for Torus {
+  type Tile = (usize, usize);
+
+  fn tiles(&self) -> impl Iterator<Item = Self::Tile> {
+    grid(self.width, self.height)
+  }
+
+  fn neighbours(&self, &(x, y): &Self::Tile) -> Vec<Self::Tile> {
+    // empty boards have no tiles to wrap around onto
+    if self.width == 0 || self.height == 0 {
+      return vec![];
+    }
+
+    let mut tiles = vec![];
+    for dy in -1..=1 {
+      for dx in -1..=1 {
+        let tile = (wrap(x, dx, self.width), wrap(y, dy, self.height));
+        // small boards can wrap around onto the same tile
+        if tile != (x, y) && !tiles.contains(&tile) {
+          tiles.push(tile);
+        }
+      }
+    }
+    tiles
+  }
+}
+
+/// A board of hexagonal tiles, where each tile neighbours the 6 tiles around it.
+///
+/// Tiles are positioned by `(column, row)`, where odd rows are shifted half a tile right.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Hex {
+  width: usize,
+  height: usize,
+}
+
+impl Hex {
+  /// Constructs a hexagonal board with the given number of columns and rows
+  pub fn new(width: usize, height: usize) -> Self {
+    Self { width, height }
+  }
+}
+
+impl Topology for Hex {
+  type Tile = (usize, usize);
+
+  fn tiles(&self) -> impl Iterator<Item = Self::Tile> {
+    grid(self.width, self.height)
+  }
+
+  fn neighbours(&self, &(x, y): &Self::Tile) -> Vec<Self::Tile> {
+    // the columns of adjacent rows that touch this tile
+    let diagonals = if y % 2 == 0 { [-1, 0] } else { [0, 1] };
+    let offsets = [(-1, 0), (1, 0)].into_iter().chain(
+      [-1, 1]
+        .into_iter()
+        .flat_map(|dy| diagonals.map(|dx| (dx, dy))),
+    );
+
+    offsets
+      .filter_map(|(dx, dy)| Some((shift(x, dx, self.width)?, shift(y, dy, self.height)?)))
+      .collect()
+  }
+}
+
+/// A cuboid board, where each tile neighbours the 26 tiles around it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cube {
+  width: usize,
+  height: usize,
+  depth: usize,
+}
+
+impl Cube {
+  /// Constructs a cuboid board with the given number of columns, rows and layers
+  pub fn new(width: usize, height: usize, depth: usize) -> Self {
+    Self {
+      width,
+      height,
+      depth,
+    }
+  }
+}
+
+impl Topology for Cube {
+  type Tile = (usize, usize, usize);
+
+  fn tiles(&self) -> impl Iterator<Item = Self::Tile> {
+    let (width, height) = (self.width, self.height);
+    (0..self.depth).flat_map(move |z| grid(width, height).map(move |(x, y)| (x, y, z)))
+  }
+
+  fn neighbours(&self, &(x, y, z): &Self::Tile) -> Vec<Self::Tile> {
+    let mut tiles = vec![];
+    for dz in -1..=1 {
+      for (x0, y0) in Square::new(self.width, self.height)
+        .neighbours(&(x, y))
+        .into_iter()
+        .chain([(x, y)])
+      {
+        let Some(z0) = shift(z, dz, self.depth) else {
+          continue;
+        };
+        if (x0, y0, z0) != (x, y, z) {
+          tiles.push((x0, y0, z0));
+        }
+      }
+    }
+    tiles
+  }
+}
+
+/// A board with any tiles and neighbourhoods.
+///
+/// ## Examples
+///
+/// ```
+/// # use farc3::systems::mines::topology::{Custom, Square, Topology};
+/// // clues count mines a knight's move away
+/// let knights = Custom::new(Square::new(8, 8).tiles(), |&(x, y): &(usize, usize)| {
+///   [(1, 2), (2, 1), (2, -1), (1, -2), (-1, -2), (-2, -1), (-2, 1), (-1, 2)]
+///     .into_iter()
+///     .filter_map(move |(dx, dy)| Some((x.checked_add_signed(dx)?, y.checked_add_signed(dy)?)))
+///     .filter(|&(x, y)| x < 8 && y < 8)
+/// });
+///
+/// assert_eq!(knights.neighbours(&(0, 0)).len(), 2);
+/// assert_eq!(knights.neighbours(&(4, 4)).len(), 8);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Custom<T, F> {
+  tiles: Vec<T>,
+  neighbours: F,
+}
+
+impl<T, F> Custom<T, F> {
+  /// Constructs a board from its tiles and a neighbourhood function
+  ///
+  /// ## Arguments
+  ///
+  /// - `tiles`: all tiles on the board
+  /// - `neighbours`: the tiles that a clue at a given tile counts mines in,\
+  ///   which should only return tiles on the board
+  pub fn new(tiles: impl IntoIterator<Item = T>, neighbours: F) -> Self {
+    Self {
+      tiles: tiles.into_iter().collect(),
+      neighbours,
+    }
+  }
+}
+
+impl<T, F, I> Topology for Custom<T, F>
+where
+  T: Hash + Eq + Clone + Debug,
+  F: Fn(&T) -> I,
+  I: IntoIterator<Item = T>,
+{
+  type Tile = T;
+
+  fn tiles(&self) -> impl Iterator<Item = Self::Tile> {
+    self.tiles.iter().cloned()
+  }
+
+  fn neighbours(&self, tile: &Self::Tile) -> Vec<Self::Tile> {
+    (self.neighbours)(tile)
+      .into_iter()
+      .filter(|other| other != tile)
+      .collect()
+  }
+}
@@ -22,3 +22,26 @@ pub fn choose_num(n: usize, r: usize) -> usize {
   // n! / (r! (n - r)!)
   pick / fact
 }
+
+/// Returns the natural logarithm of [`choose_num`].
+///
+/// This stays representable for far larger `n` than `choose_num` does,\
+/// which matters when counting the ways to place mines across a whole board.
+///
+/// ## Arguments
+///
+/// - `n`: how many items are available to choose from
+/// - `r`: how many items should be chosen
+#[inline]
+pub fn ln_choose(n: usize, r: usize) -> f64 {
+  debug_assert!(
+    r <= n,
+    "Unable to choose more than {} items from a collection with {} items",
+    r,
+    n
+  );
+  let r = r.min(n - r);
+  (0..r)
+    .map(|i| ((n - i) as f64).ln() - ((i + 1) as f64).ln())
+    .sum()
+}
@@ -1,8 +1,9 @@
 //! Example constraints and assignments for constraint satisfaction problems
 pub mod generic;
 pub mod mines;
+pub mod parity;
 
 pub mod prelude {
   //! Common exports for constraint definitions
-  pub use super::{generic::prelude::*, mines::prelude::*};
+  pub use super::{generic::prelude::*, mines::prelude::*, parity::prelude::*};
 }
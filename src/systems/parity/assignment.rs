@@ -0,0 +1,202 @@
+//! Assignments of boolean variables in parity systems
+
+use std::collections::{HashSet, hash_set};
+use std::hash::Hash;
+
+use crate::assignment::Assignment;
+use crate::utils::NewHashSet;
+
+/// An assignment of `true` / `false` values to boolean variables.\
+/// This keeps track of which variables are set and which are unset.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ParityAssignment<V: Hash + Eq> {
+  unset_vars: NewHashSet<V>,
+  set_vars: NewHashSet<V>,
+}
+
+impl<V: Hash + Eq> Default for ParityAssignment<V> {
+  fn default() -> Self {
+    Self {
+      unset_vars: Default::default(),
+      set_vars: Default::default(),
+    }
+  }
+}
+
+impl<V: Hash + Eq> FromIterator<(V, bool)> for ParityAssignment<V> {
+  fn from_iter<T: IntoIterator<Item = (V, bool)>>(iter: T) -> Self {
+    let mut unset_vars = NewHashSet::default();
+    let mut set_vars = NewHashSet::default();
+    for (var, value) in iter {
+      if value {
+        set_vars.insert(var);
+      } else {
+        unset_vars.insert(var);
+      }
+    }
+
+    Self {
+      unset_vars,
+      set_vars,
+    }
+  }
+}
+impl<V: Hash + Eq, const N: usize> From<[(V, bool); N]> for ParityAssignment<V> {
+  fn from(value: [(V, bool); N]) -> Self {
+    Self::from_iter(value)
+  }
+}
+
+impl<V: Hash + Eq> ParityAssignment<V> {
+  /// Constructs a new parity assignment from the variables that are unset and those that are set.
+  pub fn new(
+    unset_vars: impl IntoIterator<Item = V>,
+    set_vars: impl IntoIterator<Item = V>,
+  ) -> Self {
+    Self {
+      unset_vars: NewHashSet::from_iter(unset_vars),
+      set_vars: NewHashSet::from_iter(set_vars),
+    }
+  }
+
+  /// Constructs a parity assignment where all variables are `false`.
+  pub fn all_unset(unset_vars: impl Into<NewHashSet<V>>) -> Self {
+    Self {
+      unset_vars: unset_vars.into(),
+      set_vars: NewHashSet::default(),
+    }
+  }
+
+  /// Constructs a parity assignment where all variables are `true`.
+  pub fn all_set(set_vars: impl Into<NewHashSet<V>>) -> Self {
+    Self {
+      unset_vars: NewHashSet::default(),
+      set_vars: set_vars.into(),
+    }
+  }
+}
+
+impl<V: Hash + Eq + Clone> Assignment for ParityAssignment<V> {
+  type Var = V;
+  type Value = bool;
+  /// The contradicting variable, and whether `self` assigned it `true`
+  type Conflict = (V, bool);
+
+  fn get(&self, var: &V) -> Option<&bool> {
+    if self.unset_vars.contains(var) {
+      return Some(&false);
+    }
+    if self.set_vars.contains(var) {
+      return Some(&true);
+    }
+    None
+  }
+
+  fn len(&self) -> usize {
+    self.unset_vars.len() + self.set_vars.len()
+  }
+
+  fn iter(&self) -> impl Iterator<Item = (&V, &bool)> {
+    let unset_vars = self.unset_vars.iter().map(|var| (var, &false));
+    let set_vars = self.set_vars.iter().map(|var| (var, &true));
+    unset_vars.chain(set_vars)
+  }
+
+  fn insert(&mut self, var: V, value: bool) -> Option<bool> {
+    let previous = match (self.unset_vars.remove(&var), self.set_vars.remove(&var)) {
+      (true, _) => Some(false),
+      (_, true) => Some(true),
+      _ => None,
+    };
+    match value {
+      true => self.set_vars.insert(var),
+      false => self.unset_vars.insert(var),
+    };
+    previous
+  }
+
+  fn restrict_to(mut self, vars: impl IntoIterator<Item = V>) -> Self {
+    let vars: HashSet<V> = vars.into_iter().collect();
+    self.unset_vars.retain(|var| vars.contains(var));
+    self.set_vars.retain(|var| vars.contains(var));
+    self
+  }
+
+  fn intersection(mut self, other: Self) -> Self {
+    self.unset_vars.retain(|var| other.unset_vars.contains(var));
+    self.set_vars.retain(|var| other.set_vars.contains(var));
+    self
+  }
+
+  fn union(mut self, other: Self) -> Self {
+    // calculate union of unset and set variables
+    for var in other.unset_vars {
+      self.unset_vars.insert(var);
+    }
+    for var in other.set_vars {
+      self.set_vars.insert(var);
+    }
+
+    // remove conflicting assignments
+    self.unset_vars.retain(|var| {
+      let shared = self.set_vars.contains(var);
+      if shared {
+        self.set_vars.remove(var);
+      }
+      !shared
+    });
+
+    self
+  }
+
+  fn checked_union(self, other: Self) -> Result<Self, Vec<Self::Conflict>> {
+    let conflicts: Vec<_> = self
+      .unset_vars
+      .intersection(&other.set_vars)
+      .map(|var| (var.clone(), false))
+      .chain(
+        self
+          .set_vars
+          .intersection(&other.unset_vars)
+          .map(|var| (var.clone(), true)),
+      )
+      .collect();
+    if !conflicts.is_empty() {
+      return Err(conflicts);
+    }
+    Ok(self.union(other))
+  }
+}
+
+impl<V: Hash + Eq> IntoIterator for ParityAssignment<V> {
+  type Item = (V, bool);
+  type IntoIter = IntoIter<V>;
+  fn into_iter(self) -> Self::IntoIter {
+    IntoIter {
+      unset_vars: self.unset_vars.into_iter(),
+      set_vars: self.set_vars.into_iter(),
+    }
+  }
+}
+
+/// An iterator for variables assigned by a [`ParityAssignment`].\
+/// This'll yield:
+/// - `(var, true)` if a variable is set
+/// - `(var, false)` if a variable is unset
+pub struct IntoIter<V> {
+  unset_vars: hash_set::IntoIter<V>,
+  set_vars: hash_set::IntoIter<V>,
+}
+
+impl<V> Iterator for IntoIter<V> {
+  type Item = (V, bool);
+  fn next(&mut self) -> Option<Self::Item> {
+    if let Some(var) = self.unset_vars.next() {
+      return Some((var, false));
+    }
+    if let Some(var) = self.set_vars.next() {
+      return Some((var, true));
+    }
+    None
+  }
+}
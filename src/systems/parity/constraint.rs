@@ -0,0 +1,183 @@
+//! Constraints for the parity of sets of boolean variables
+
+use std::collections::HashSet;
+use std::hash::Hash;
+use std::mem;
+
+use super::{assignment::ParityAssignment, errors::ParityConflicts};
+use crate::{assignment::Assignment, constraint::Constraint, utils::NewHashSet};
+
+/// A constraint that the XOR of the given boolean variables equals `parity`.\
+/// Equivalently, an odd number of the variables are `true` exactly when `parity` is `true`.
+///
+/// Reducing parity constraints performs Gaussian elimination over GF(2),\
+/// where each constraint is a row and adding rows is taking their XOR.\
+/// The pivot of each row is its smallest variable,\
+/// so minimising a system brings it into reduced row echelon form.
+#[derive(Default, Debug, Hash, PartialEq, Eq, Clone)]
+pub struct ParityConstraint<V: Hash + Eq> {
+  /// The variables that are XOR-ed together
+  vars: NewHashSet<V>,
+  /// The value of the XOR of all variables
+  parity: bool,
+}
+
+impl<V: Hash + Eq> ParityConstraint<V> {
+  /// Constructs a parity constraint
+  ///
+  /// ## Arguments
+  ///
+  /// - `vars`: the variables that are XOR-ed together
+  /// - `parity`: the value of the XOR of `vars`
+  ///
+  /// ## Returns
+  ///
+  /// A new [`ParityConstraint`]
+  ///
+  /// ## Examples
+  ///
+  /// ```
+  /// # use std::collections::HashMap;
+  /// # use farc3::prelude::*;
+  /// // 0 ^ 1 = 1, 0 ^ 1 ^ 2 = 1 and 1 ^ 2 = 0 have a single solution
+  /// let mut sys = System::from([
+  ///   ParityConstraint::new([0, 1], true),
+  ///   ParityConstraint::new([0, 1, 2], true),
+  ///   ParityConstraint::new([1, 2], false),
+  /// ]);
+  ///
+  /// let sltn = sys.pop_solution().unwrap();
+  /// assert_eq!(
+  ///   HashMap::from_iter(sltn),
+  ///   HashMap::from([(0, true), (1, false), (2, false)])
+  /// );
+  /// ```
+  pub fn new(vars: impl IntoIterator<Item = V>, parity: bool) -> Self {
+    Self {
+      vars: NewHashSet::from_iter(vars),
+      parity,
+    }
+  }
+
+  /// The variables that are XOR-ed together
+  pub fn vars(&self) -> &HashSet<V> {
+    &self.vars
+  }
+
+  /// The value of the XOR of all variables
+  pub fn parity(&self) -> bool {
+    self.parity
+  }
+}
+
+impl<V: Hash + Eq + Ord + Clone> Constraint for ParityConstraint<V> {
+  type Var = V;
+  type Solution = ParityAssignment<V>;
+  type ConflictErr = ParityConflicts<V>;
+
+  fn size(&self) -> usize {
+    // any assignment to all but one variable can be completed by the last variable
+    match self.vars.len() {
+      0 => !self.parity as usize,
+      len => 1usize.checked_shl(len as u32 - 1).unwrap_or(usize::MAX),
+    }
+  }
+
+  fn variables(&self) -> impl Iterator<Item = Self::Var> {
+    self.vars.iter().cloned()
+  }
+
+  /// Decomposes on the value of a single variable
+  fn decompositions(&self) -> impl Iterator<Item = Self> {
+    let var = self.vars.iter().next();
+    var.and_then(|var| self.split_on(var)).into_iter().flatten()
+  }
+
+  /// Eliminates the pivot of `other`, its smallest variable, from `self`
+  fn reduce(&mut self, other: &Self) -> Result<bool, Self::ConflictErr> {
+    // adding `other` to `self` gives the XOR of their variables,
+    // which always removes the pivot and leaves smaller variables untouched
+    let Some(pivot) = other.vars.iter().min() else {
+      return Ok(false);
+    };
+    if !self.vars.contains(pivot) {
+      return Ok(false);
+    }
+    let vars: NewHashSet<_> = self
+      .vars
+      .symmetric_difference(&other.vars)
+      .cloned()
+      .collect();
+
+    let parity = self.parity ^ other.parity;
+    // conflict when no variables are left to give an odd parity
+    if vars.is_empty() && parity {
+      return Err(ParityConflicts {
+        constraint: self.clone(),
+        other: other.clone(),
+      });
+    }
+
+    self.vars = vars;
+    self.parity = parity;
+    Ok(true)
+  }
+
+  fn pop_solution(&mut self) -> Option<Self::Solution> {
+    if self.vars.len() != 1 {
+      return None;
+    }
+
+    let vars = mem::take(&mut self.vars);
+    let sltn = match mem::take(&mut self.parity) {
+      true => Self::Solution::all_set(vars),
+      false => Self::Solution::all_unset(vars),
+    };
+    Some(sltn)
+  }
+
+  fn is_satisfied(&self, solution: &Self::Solution) -> bool {
+    let mut parity = false;
+    for var in self.vars.iter() {
+      match solution.get(var) {
+        Some(&set) => parity ^= set,
+        None => return false,
+      }
+    }
+    parity == self.parity
+  }
+
+  /// Excluding a decomposition assigns the opposite value to its variable
+  fn exclude(&self, decomposition: &Self) -> Option<Self> {
+    if decomposition.vars.len() != 1 {
+      return None;
+    }
+    Some(Self {
+      vars: decomposition.vars.clone(),
+      parity: !decomposition.parity,
+    })
+  }
+
+  fn split_on(&self, var: &V) -> Option<Vec<Self>> {
+    if !self.vars.contains(var) {
+      return None;
+    }
+
+    // a single variable is forced to take the parity
+    let values = match self.vars.len() {
+      1 => vec![self.parity],
+      _ => vec![true, false],
+    };
+    let vars = NewHashSet::from([var.clone()]);
+    let splits = values.into_iter().map(|parity| Self {
+      vars: vars.clone(),
+      parity,
+    });
+    Some(splits.collect())
+  }
+
+  fn subsumes(&self, other: &Self) -> bool {
+    // a constraint on no variables is always satisfied with even parity
+    other.vars.is_empty() && !other.parity || self == other
+  }
+}
@@ -0,0 +1,75 @@
+//! Errors produced when parity constraints conflict
+
+use std::error::Error;
+use std::fmt::{self, Debug, Display};
+use std::hash::Hash;
+
+use super::constraint::ParityConstraint;
+
+/// The error that is produced when 2 parity constraints conflict.
+///
+/// Constraints conflict when adding them together gives `0 = 1`,\
+/// that is they XOR the same variables to different parities.
+///
+/// ## Example
+///
+/// ```
+/// # use farc3::constraint::Constraint;
+/// # use farc3::systems::parity::{
+/// #   constraint::ParityConstraint,
+/// #   errors::ParityConflicts
+/// # };
+///
+/// let mut cons0 = ParityConstraint::new([0, 1], true);
+/// let cons1 = ParityConstraint::new([0, 1], false);
+///
+/// let res = cons0.reduce(&cons1);
+/// assert_eq!(
+///   res,
+///   Err(ParityConflicts {
+///     constraint: cons0.clone(),
+///     other: cons1,
+///   })
+/// );
+/// ```
+#[derive(Clone, PartialEq, Eq)]
+pub struct ParityConflicts<V: Hash + Eq> {
+  /// The constraint being reduced
+  pub constraint: ParityConstraint<V>,
+  /// The constraint it was reduced by
+  pub other: ParityConstraint<V>,
+}
+
+/// Only the sizes and parities of the constraints are shown,\
+/// so that variables don't need to implement `Debug`.\
+/// The [`Display`] implementation shows the variables themselves.
+impl<V: Hash + Eq> Debug for ParityConflicts<V> {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.debug_struct("ParityConflicts")
+      .field("constraint", &row(&self.constraint))
+      .field("other", &row(&self.other))
+      .finish()
+  }
+}
+
+impl<V: Hash + Eq + Debug> Display for ParityConflicts<V> {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(
+      f,
+      "cannot reduce {:?} by {:?}, as together they give 0 = 1",
+      self.constraint, self.other
+    )
+  }
+}
+
+impl<V: Hash + Eq + Debug> Error for ParityConflicts<V> {}
+
+/// Shows the size and parity of a constraint, without its variables
+fn row<V: Hash + Eq>(constraint: &ParityConstraint<V>) -> impl Debug + '_ {
+  fmt::from_fn(move |f| {
+    f.debug_struct("ParityConstraint")
+      .field("vars", &constraint.vars().len())
+      .field("parity", &constraint.parity())
+      .finish()
+  })
+}
@@ -0,0 +1,13 @@
+//! Constraint Satisfaction Problems over parities of boolean variables,\
+//! such as Lights Out and parity variants of minesweeper.
+pub mod assignment;
+pub mod constraint;
+pub mod errors;
+
+pub mod prelude {
+  //! Common exports for parity systems
+  pub use super::constraint::ParityConstraint;
+}
+
+#[cfg(test)]
+mod test;
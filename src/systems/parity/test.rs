@@ -0,0 +1,385 @@
+/// Unit testing [`ParityAssignment`]s
+///
+/// [`ParityAssignment`]: crate::systems::parity::assignment::ParityAssignment
+mod assignments {
+  use super::super::assignment::ParityAssignment;
+  use crate::prelude::Assignment;
+  use std::collections::{HashMap, HashSet};
+
+  #[test]
+  fn construction() {
+    ParityAssignment::new([0, 2], [1, 3]);
+  }
+
+  #[test]
+  fn iteration() {
+    let assign = ParityAssignment::from_iter([(0, true), (1, false), (2, true)]);
+    assert_eq!(
+      HashMap::from_iter(assign),
+      HashMap::from([(0, true), (1, false), (2, true)])
+    );
+  }
+
+  #[test]
+  fn intersection() {
+    let assign0 = ParityAssignment::from_iter([(0, true), (1, false), (2, true)]);
+    let assign1 = ParityAssignment::from_iter([/*      */ (1, false), (2, false), (3, true)]);
+    let assign = assign0.intersection(assign1);
+
+    assert_eq!(
+      HashMap::from_iter(assign),
+      HashMap::from([
+        /* 0 not common to both assignments */
+        (1, false),
+        /* 2 is contradictory */
+        /* 3 not common to both assignments */
+      ])
+    );
+  }
+
+  #[test]
+  fn union() {
+    let assign0 = ParityAssignment::from_iter([(0, true), (1, false), (2, true)]);
+    let assign1 = ParityAssignment::from_iter([/*      */ (1, false), (2, false), (3, true)]);
+    let assign = assign0.union(assign1);
+
+    assert_eq!(
+      HashMap::from_iter(assign),
+      HashMap::from([
+        (0, true),
+        (1, false),
+        /* 2 is contradictory */
+        (3, true)
+      ])
+    );
+  }
+
+  #[test]
+  fn checked_union() {
+    let assign0 = ParityAssignment::from_iter([(0, true), (1, false)]);
+    let assign1 = ParityAssignment::from_iter([/*      */ (1, false), (3, true)]);
+    let assign = assign0.checked_union(assign1).unwrap();
+    assert_eq!(
+      HashMap::from_iter(assign),
+      HashMap::from([(0, true), (1, false), (3, true)])
+    );
+
+    let assign0 = ParityAssignment::from_iter([(0, true), (1, false), (2, true)]);
+    let assign1 = ParityAssignment::from_iter([(0, false), (1, false), (2, false)]);
+    let conflicts: HashSet<_> = assign0
+      .checked_union(assign1)
+      .unwrap_err()
+      .into_iter()
+      .collect();
+    assert_eq!(conflicts, HashSet::from([(0, true), (2, true)]));
+  }
+
+  #[test]
+  fn lookup() {
+    let mut assign = ParityAssignment::from_iter([(0, true), (1, false)]);
+    assert_eq!(assign.get(&0), Some(&true));
+    assert_eq!(assign.get(&1), Some(&false));
+    assert_eq!(assign.get(&2), None);
+    assert!(assign.contains(&1));
+    assert_eq!(assign.len(), 2);
+    assert!(!assign.is_empty());
+    assert_eq!(
+      assign
+        .iter()
+        .map(|(&var, &value)| (var, value))
+        .collect::<HashMap<_, _>>(),
+      HashMap::from([(0, true), (1, false)])
+    );
+
+    assert_eq!(assign.insert(0, false), Some(true));
+    assert_eq!(assign.insert(2, true), None);
+    assert_eq!(assign.get(&0), Some(&false));
+    assert_eq!(assign.len(), 3);
+
+    let assign = assign.restrict_to([0, 2, 3]);
+    assert_eq!(
+      HashMap::from_iter(assign),
+      HashMap::from([(0, false), (2, true)])
+    );
+  }
+}
+
+/// Unit testing [`ParityConstraint`]s
+///
+/// [`ParityConstraint`]: crate::systems::parity::constraint::ParityConstraint
+mod constraints {
+  use super::super::{constraint::ParityConstraint, errors::ParityConflicts};
+  use crate::prelude::Constraint;
+  use std::collections::{HashMap, HashSet};
+
+  #[test]
+  fn construction() {
+    ParityConstraint::new([0, 1, 2], false); // even number set
+    ParityConstraint::new([0, 1, 2], true); // odd number set
+    ParityConstraint::new([0], true); // decided
+  }
+
+  #[test]
+  fn size() {
+    let cons = ParityConstraint::<i32>::new([], false);
+    assert_eq!(cons.size(), 1); // {}
+
+    let cons = ParityConstraint::<i32>::new([], true);
+    assert_eq!(cons.size(), 0);
+
+    let cons = ParityConstraint::new([0], true);
+    assert_eq!(cons.size(), 1); // {0}
+
+    let cons = ParityConstraint::new([0, 1, 2], true);
+    assert_eq!(cons.size(), 4); // {0}, {1}, {2}, {0, 1, 2}
+  }
+
+  #[test]
+  fn variables() {
+    let cons = ParityConstraint::new([0, 1, 2], true);
+    assert_eq!(
+      HashSet::from_iter(cons.variables()),
+      HashSet::from([0, 1, 2])
+    );
+  }
+
+  #[test]
+  fn decompositions() {
+    let cons = ParityConstraint::new([0, 1, 2], true);
+    let vars = HashSet::from([0, 1, 2]);
+
+    for decomp in cons.decompositions() {
+      assert_eq!(decomp.size(), 1);
+      let vars0 = decomp.variables().collect();
+      assert!(vars.is_superset(&vars0));
+    }
+    // decompositions partition the solutions on a single variable
+    let decomps: Vec<_> = cons.decompositions().collect();
+    assert_eq!(decomps.len(), 2);
+    assert_eq!(
+      decomps[0].variables().collect::<Vec<_>>(),
+      decomps[1].variables().collect::<Vec<_>>()
+    );
+    assert_ne!(decomps[0], decomps[1]);
+
+    let cons = ParityConstraint::new([0], false);
+    let decomps: Vec<_> = cons.decompositions().collect();
+    assert_eq!(decomps, vec![cons]);
+  }
+
+  #[test]
+  fn pop_solution() {
+    let mut cons = ParityConstraint::new([0, 1], true);
+    assert_eq!(cons.pop_solution(), None);
+
+    let mut cons = ParityConstraint::new([0], true);
+    let sltn = cons.pop_solution().unwrap();
+    assert_eq!(HashMap::from_iter(sltn), HashMap::from([(0, true)]));
+
+    assert_eq!(cons.size(), 1);
+    assert_eq!(HashSet::from_iter(cons.variables()), HashSet::from([]));
+  }
+
+  /// Parity constraint reduction eliminates variables shared with a subset
+  #[test]
+  fn reduce_subset() {
+    let mut cons0 = ParityConstraint::new([0, 1, 2], true);
+    let cons1 = ParityConstraint::new([0, 1], true);
+
+    assert_eq!(cons0.reduce(&cons1), Ok(true));
+    assert_eq!(cons0, ParityConstraint::new([2], false));
+
+    let sltn = cons0.pop_solution().unwrap();
+    assert_eq!(HashMap::from_iter(sltn), HashMap::from([(2, false)]));
+  }
+
+  /// Parity constraint reduction eliminates the pivot (smallest variable) of the other constraint
+  #[test]
+  fn reduce_overlap() {
+    let mut cons0 = ParityConstraint::new([0, 1, 2, 3], true);
+    let cons1 = ParityConstraint::new([1, 2, 3, 4], false);
+
+    assert_eq!(cons0.reduce(&cons1), Ok(true));
+    assert_eq!(cons0, ParityConstraint::new([0, 4], true));
+
+    // the pivot of `cons0` isn't in `cons1`
+    let mut cons1 = cons1;
+    assert_eq!(cons1.reduce(&cons0), Ok(false));
+
+    // eliminating the pivot can introduce other variables
+    let mut cons0 = ParityConstraint::new([0, 1], true);
+    assert_eq!(
+      cons0.reduce(&ParityConstraint::new([1, 2], false)),
+      Ok(true)
+    );
+    assert_eq!(cons0, ParityConstraint::new([0, 2], true));
+  }
+
+  /// Parity constraints conflict when their XOR gives an odd parity with no variables
+  #[test]
+  fn reduce_conflict() {
+    let mut cons0 = ParityConstraint::new([0, 1], true);
+    let cons1 = ParityConstraint::new([0, 1], false);
+    assert_eq!(
+      cons0.reduce(&cons1),
+      Err(ParityConflicts {
+        constraint: ParityConstraint::new([0, 1], true),
+        other: cons1,
+      })
+    );
+    // the constraint is left unchanged on a conflict
+    assert_eq!(cons0, ParityConstraint::new([0, 1], true));
+
+    let mut cons0 = ParityConstraint::new([0, 1], true);
+    let cons1 = ParityConstraint::new([0, 1], true);
+    assert_eq!(cons0.reduce(&cons1), Ok(true));
+    assert_eq!(cons0.size(), 1);
+  }
+
+  #[test]
+  fn satisfied() {
+    let cons = ParityConstraint::new([0, 1, 2], true);
+    assert!(cons.is_satisfied(&[(0, true), (1, false), (2, false)].into_iter().collect()));
+    assert!(cons.is_satisfied(&[(0, true), (1, true), (2, true)].into_iter().collect()));
+    assert!(!cons.is_satisfied(&[(0, true), (1, true), (2, false)].into_iter().collect()));
+    assert!(!cons.is_satisfied(&[(0, true), (1, false)].into_iter().collect()));
+  }
+
+  #[test]
+  fn split_on() {
+    let cons = ParityConstraint::new([0, 1, 2], true);
+    assert_eq!(
+      cons.split_on(&1),
+      Some(vec![
+        ParityConstraint::new([1], true),
+        ParityConstraint::new([1], false)
+      ])
+    );
+    assert_eq!(cons.split_on(&3), None);
+  }
+}
+
+/// Testing parity constraint compatability with [`System`] solving
+///
+/// [`System`]: crate::system::System
+mod solver {
+  use std::collections::{HashMap, HashSet};
+
+  use crate::prelude::{ParityConstraint, System};
+  use crate::systems::parity::assignment::ParityAssignment;
+
+  #[test]
+  fn unresolvable() {
+    let cons = ParityConstraint::new([0, 1], true);
+
+    let mut sys = System::from([cons]);
+    let sltn = sys.pop_solution().unwrap();
+    assert_eq!(HashMap::from_iter(sltn), HashMap::from([]));
+    assert_eq!(sys.len(), 1);
+  }
+
+  /// The solver can fully solve a system by elimination
+  #[test]
+  fn full_minimise() {
+    let cons0 = ParityConstraint::new([0, 1, 2], true);
+    let cons1 = ParityConstraint::new([1, 2], false);
+    let cons2 = ParityConstraint::new([0, 1], false);
+
+    let mut sys = System::from([cons0, cons1, cons2]);
+    let sltn = sys.pop_solution().unwrap();
+    assert_eq!(
+      HashMap::from_iter(sltn),
+      HashMap::from([(0, true), (1, true), (2, true)])
+    );
+    assert_eq!(sys.len(), 0);
+  }
+
+  /// Elimination solves systems where no pair of constraints shrinks the other
+  #[test]
+  fn row_echelon() {
+    let mut sys = System::from([
+      ParityConstraint::new([0, 1], true),
+      ParityConstraint::new([0, 2], false),
+      ParityConstraint::new([0, 3], true),
+      ParityConstraint::new([1, 2, 3], true),
+    ]);
+
+    let sltn = sys.pop_solution().unwrap();
+    assert_eq!(
+      HashMap::from_iter(sltn),
+      HashMap::from([(0, true), (1, false), (2, true), (3, false)])
+    );
+    assert!(sys.is_empty());
+  }
+
+  #[test]
+  fn conflict() {
+    let cons0 = ParityConstraint::new([0, 1], true);
+    let cons1 = ParityConstraint::new([1, 2], true);
+    let cons2 = ParityConstraint::new([0, 2], true);
+
+    let sys = System::from([cons0, cons1, cons2]);
+    assert_eq!(sys.solve().count(), 0);
+  }
+
+  #[test]
+  fn solutions() {
+    let cons0 = ParityConstraint::new([0, 1, 2], true);
+    let cons1 = ParityConstraint::new([1, 2], false);
+
+    let sys = System::from([cons0, cons1]);
+
+    let sltns: HashSet<_> = sys.solve().collect();
+    assert_eq!(
+      sltns,
+      HashSet::from([
+        ParityAssignment::new([1, 2], [0]),
+        ParityAssignment::new([], [0, 1, 2]),
+      ])
+    );
+  }
+
+  /// Solving a 3x3 game of Lights Out, where every light starts on
+  #[test]
+  fn lights_out() {
+    let lights: Vec<(i32, i32)> = (0..3).flat_map(|y| (0..3).map(move |x| (x, y))).collect();
+    let toggled_by = |&(x, y): &(i32, i32)| {
+      [(x, y), (x - 1, y), (x + 1, y), (x, y - 1), (x, y + 1)]
+        .into_iter()
+        .filter(|press| lights.contains(press))
+        .collect::<Vec<_>>()
+    };
+
+    let sys: System<_> = lights
+      .iter()
+      .map(|light| ParityConstraint::new(toggled_by(light), true))
+      .collect();
+    let sltns: HashSet<_> = sys.solve().collect();
+    assert_eq!(sltns.len(), 1);
+
+    // pressing the solution toggles every light an odd number of times
+    let presses = HashMap::<_, _>::from_iter(sltns.into_iter().next().unwrap());
+    for light in &lights {
+      let count = toggled_by(light)
+        .iter()
+        .filter(|press| presses[press])
+        .count();
+      assert_eq!(count % 2, 1);
+    }
+  }
+
+  /// Each solution is only found once
+  #[test]
+  fn unique_solutions() {
+    let sys = System::from([
+      ParityConstraint::new([0, 1, 2, 3], true),
+      ParityConstraint::new([2, 3, 4], false),
+      ParityConstraint::new([4, 5], true),
+    ]);
+
+    let count = sys.clone().solve().count();
+    let sltns: HashSet<_> = sys.solve().collect();
+    assert_eq!(count, sltns.len());
+    assert_eq!(count, 8);
+  }
+}